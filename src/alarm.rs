@@ -0,0 +1,178 @@
+use crate::{
+    common::Toggle,
+    constants::TICK_VALUE_MS,
+    duration::{self, ONE_MINUTE, ONE_SECOND},
+    notification::NotificationKind,
+    widgets::clock,
+};
+use color_eyre::{Report, eyre::ensure};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Number of repeats to reach full volume if `volume_ramp` is enabled
+#[cfg(feature = "sound")]
+const VOLUME_RAMP_STEPS: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlarmSettings {
+    /// Time between repeats
+    pub interval: Duration,
+    /// Maximum time to repeat. `Duration::ZERO` means no limit.
+    pub max_duration: Duration,
+    /// Increase volume of repeated sounds
+    pub volume_ramp: Toggle,
+}
+
+impl Default for AlarmSettings {
+    fn default() -> Self {
+        Self {
+            interval: ONE_SECOND * 10,
+            max_duration: ONE_MINUTE * 5,
+            volume_ramp: Toggle::Off,
+        }
+    }
+}
+
+/// Parses interval of an alarm by using `duration::parse_long_duration`.
+/// It has to be one second at least.
+pub fn parse_alarm_interval(arg: &str) -> Result<Duration, Report> {
+    let interval = duration::parse_long_duration(arg)?;
+    ensure!(
+        interval >= ONE_SECOND,
+        "Interval must be 1 second at least."
+    );
+    Ok(interval)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlarmAction {
+    /// Nothing to do
+    None,
+    /// Repeat sound and notification
+    Repeat,
+    /// Maximum duration reached
+    Stop,
+}
+
+/// Alarm of a finished clock, which is active until it's acknowledged.
+#[derive(Debug, Clone)]
+pub struct Alarm {
+    pub kind: NotificationKind,
+    pub message: String,
+    elapsed: Duration,
+    repeats: u32,
+    done_count: Option<u64>,
+}
+
+impl Alarm {
+    pub fn new(kind: NotificationKind, message: String) -> Self {
+        Self {
+            kind,
+            message,
+            elapsed: Duration::ZERO,
+            repeats: 0,
+            done_count: Some(clock::MAX_DONE_COUNT),
+        }
+    }
+
+    /// Counts elapsed time of an alarm.
+    /// It should be called whenever `TuiEvent::Tick` is handled.
+    pub fn tick(&mut self, settings: &AlarmSettings) -> AlarmAction {
+        self.elapsed += Duration::from_millis(TICK_VALUE_MS);
+        // Blink endlessly by starting to count `done` again
+        self.done_count = clock::count_clock_done(self.done_count).or(Some(clock::MAX_DONE_COUNT));
+
+        if !settings.max_duration.is_zero() && self.elapsed >= settings.max_duration {
+            AlarmAction::Stop
+        } else if self.elapsed >= settings.interval * (self.repeats + 1) {
+            self.repeats += 1;
+            AlarmAction::Repeat
+        } else {
+            AlarmAction::None
+        }
+    }
+
+    pub fn should_blink(&self) -> bool {
+        clock::should_blink(self.done_count)
+    }
+
+    /// Volume (0.0-1.0) of current repeat.
+    /// It increases with each repeat, if `volume_ramp` is enabled.
+    #[cfg(feature = "sound")]
+    pub fn get_volume(&self, settings: &AlarmSettings) -> f32 {
+        match settings.volume_ramp {
+            Toggle::On => ((self.repeats + 1) as f32 / VOLUME_RAMP_STEPS as f32).min(1.0),
+            Toggle::Off => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick_until(
+        alarm: &mut Alarm,
+        settings: &AlarmSettings,
+        duration: Duration,
+    ) -> Vec<AlarmAction> {
+        let ticks = duration.as_millis() as u64 / TICK_VALUE_MS;
+        (0..ticks).map(|_| alarm.tick(settings)).collect()
+    }
+
+    #[test]
+    fn test_alarm_repeat() {
+        let settings = AlarmSettings::default();
+        let mut alarm = Alarm::new(NotificationKind::Countdown, "done".into());
+        let actions = tick_until(&mut alarm, &settings, ONE_SECOND * 30);
+        let repeats = actions
+            .iter()
+            .filter(|a| **a == AlarmAction::Repeat)
+            .count();
+        // every 10 seconds
+        assert_eq!(repeats, 3);
+        assert_eq!(actions.last(), Some(&AlarmAction::Repeat));
+    }
+
+    #[test]
+    fn test_alarm_stop() {
+        let settings = AlarmSettings {
+            max_duration: ONE_SECOND * 15,
+            ..AlarmSettings::default()
+        };
+        let mut alarm = Alarm::new(NotificationKind::Countdown, "done".into());
+        let actions = tick_until(&mut alarm, &settings, ONE_SECOND * 15);
+        assert_eq!(actions.last(), Some(&AlarmAction::Stop));
+
+        // no limit
+        let settings = AlarmSettings {
+            max_duration: Duration::ZERO,
+            ..AlarmSettings::default()
+        };
+        let mut alarm = Alarm::new(NotificationKind::Countdown, "done".into());
+        let actions = tick_until(&mut alarm, &settings, ONE_MINUTE * 60);
+        assert!(!actions.contains(&AlarmAction::Stop));
+    }
+
+    #[cfg(feature = "sound")]
+    #[test]
+    fn test_alarm_volume() {
+        let mut settings = AlarmSettings::default();
+        let mut alarm = Alarm::new(NotificationKind::Countdown, "done".into());
+        assert_eq!(alarm.get_volume(&settings), 1.0);
+
+        settings.volume_ramp = Toggle::On;
+        assert_eq!(alarm.get_volume(&settings), 0.2);
+        tick_until(&mut alarm, &settings, ONE_SECOND * 10);
+        assert_eq!(alarm.get_volume(&settings), 0.4);
+        tick_until(&mut alarm, &settings, ONE_MINUTE);
+        assert_eq!(alarm.get_volume(&settings), 1.0);
+    }
+
+    #[test]
+    fn test_parse_alarm_interval() {
+        assert_eq!(parse_alarm_interval("30").unwrap(), ONE_SECOND * 30);
+        assert_eq!(parse_alarm_interval("1:00").unwrap(), ONE_MINUTE);
+        assert!(parse_alarm_interval("0").is_err());
+    }
+}
@@ -1,41 +1,64 @@
 use crate::{
+    alarm::{Alarm, AlarmAction, AlarmSettings},
     args::Args,
-    common::{AppEditMode, AppTime, AppTimeFormat, ClockTypeId, Content, Style, Toggle},
+    common::{
+        AppEditMode, AppTime, AppTimeFormat, ClockTypeId, Content, ExtendSettings, QuitRunning,
+        Style, Toggle, Tone,
+    },
     constants::TICK_VALUE_MS,
+    duration::DurationEx,
     event::Event,
     events::{self, TuiEventHandler},
-    storage::AppStorage,
-    terminal::Terminal,
+    notification::{
+        self, NotificationBackend, NotificationContext, NotificationKind, NotificationSettings,
+    },
+    preset::{PresetValue, Presets},
+    schedule::ScheduledStart,
+    storage::{self, AppStorage, RunningClocks, Storage},
+    terminal::{self, Progress, Terminal},
+    urgency::UrgencyThresholds,
     widgets::{
-        clock::{self, ClockState, ClockStateArgs},
-        countdown::{Countdown, CountdownState, CountdownStateArgs},
+        alarm::AlarmBanner,
+        clock::{self, ClockSnapshot, ClockState, ClockStateArgs},
+        confirm::ConfirmPopup,
+        countdown::{Countdown, CountdownSnapshot, CountdownState, CountdownStateArgs},
         event::{EventState, EventStateArgs, EventWidget},
         footer::{Footer, FooterState},
         header::Header,
         local_time::{LocalTimeState, LocalTimeStateArgs, LocalTimeWidget},
-        pomodoro::{Mode as PomodoroMode, PomodoroState, PomodoroStateArgs, PomodoroWidget},
+        pomodoro::{
+            Mode as PomodoroMode, PomodoroSnapshot, PomodoroState, PomodoroStateArgs,
+            PomodoroWidget,
+        },
+        preset_picker::{PresetItem, PresetPicker, PresetPickerState},
         timer::{Timer, TimerState},
     },
 };
 
-use crossterm::event::Event as CrosstermEvent;
+use crossterm::event::{Event as CrosstermEvent, KeyModifiers};
 
 #[cfg(feature = "sound")]
-use crate::sound::Sound;
+use crate::sound::{Sound, SoundKind, SoundPaths};
 #[cfg(feature = "sound")]
-use std::path::PathBuf;
+use crate::storage::MAX_VOLUME;
 
 use color_eyre::Result;
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Modifier, Style as RatatuiStyle},
     widgets::{StatefulWidget, Widget},
 };
 
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
+use time::OffsetDateTime;
 use tracing::{debug, error};
 
+/// Step to change volume by keys
+#[cfg(feature = "sound")]
+const VOLUME_STEP: u8 = 10;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Mode {
     Running,
@@ -46,9 +69,66 @@ pub struct App {
     content: Content,
     mode: Mode,
     notification: Toggle,
+    notification_backends: Vec<NotificationBackend>,
+    notification_settings: NotificationSettings,
+    /// Counter to flash the screen (`NotificationBackend::Flash`)
+    flash_count: Option<u64>,
     blink: Toggle,
+    alarm: Toggle,
+    alarm_settings: AlarmSettings,
+    extend_settings: ExtendSettings,
+    presets: Presets,
+    /// Popup to pick a preset, if it's open
+    preset_picker: Option<PresetPickerState>,
+    /// Min. running time of a clock to confirm its reset. `Duration::ZERO` means no confirmation.
+    confirm_reset: Duration,
+    /// Reset (key event) waiting for its confirmation
+    pending_reset: Option<events::TuiEvent>,
+    quit_running: QuitRunning,
+    /// Quit waiting for its confirmation
+    pending_quit: bool,
+    /// Keep running clocks running after quitting
+    keep_running: bool,
+    /// Clock armed to start automatically
+    scheduled_start: Option<ScheduledStart>,
+    /// Interval to save data. `Duration::ZERO` means no autosave.
+    autosave: Duration,
+    last_save: Instant,
+    /// Flag to save data by next autosave (e.g. after significant changes)
+    save_requested: bool,
+    /// Notice to show until any key is pressed (e.g. about recovered data)
+    notice: Option<String>,
+    /// Volume of sounds (0-100)
+    volume: u8,
+    /// Name of output device to play sounds with
+    sound_device: Option<String>,
+    /// Built-in tone to play if no sound file is given
+    sound_tone: Tone,
+    /// Clock types to play `SoundKind::Tick` for
+    tick_sound: Vec<NotificationKind>,
+    /// Clock types to play `SoundKind::CountdownBeep` for
+    countdown_beep: Vec<NotificationKind>,
+    /// Latest second of a running clock a sound has been checked for
+    #[cfg(feature = "sound")]
+    last_clock_second: Option<u64>,
+    /// Alarm of a finished clock, active until it's acknowledged
+    active_alarm: Option<Alarm>,
+    urgency: Toggle,
+    urgency_thresholds: UrgencyThresholds,
+    terminal_title: Toggle,
+    terminal_progress: Toggle,
+    /// Latest title sent to terminal (to avoid sending same title again)
+    last_terminal_title: Option<String>,
+    /// Latest progress sent to terminal (to avoid sending same progress again)
+    last_terminal_progress: Option<Progress>,
     #[cfg(feature = "sound")]
     sound: Option<Sound>,
+    /// Remaining time to play `SoundKind::PreAlert`
+    #[cfg(feature = "sound")]
+    pre_alert: Option<Duration>,
+    /// Flag to play `SoundKind::PreAlert` once only
+    #[cfg(feature = "sound")]
+    pre_alert_played: bool,
     app_time: AppTime,
     app_time_format: AppTimeFormat,
     countdown: CountdownState,
@@ -61,14 +141,60 @@ pub struct App {
     vim_motions: bool,
     footer: FooterState,
     cursor_position: Option<Position>,
+    /// Show clock only (no header, no footer)
+    zen: bool,
+    /// Read-only `zen` mode, which ignores all keys except `is_kiosk_unlock_key`
+    kiosk: bool,
+}
+
+/// Values to save as soon as they have been changed (e.g. by a key).
+/// Values of edits in progress are ignored (see `ClockState::snapshot`).
+#[derive(PartialEq)]
+struct SaveState {
+    content: Content,
+    style: Style,
+    with_decis: bool,
+    show_menu: bool,
+    footer_app_time: Option<AppTimeFormat>,
+    app_time_format: AppTimeFormat,
+    volume: u8,
+    presets: Presets,
+    countdown: CountdownSnapshot,
+    timer: ClockSnapshot,
+    pomodoro: PomodoroSnapshot,
+    event: Event,
 }
 
 pub struct AppArgs {
     pub style: Style,
     pub with_decis: bool,
     pub notification: Toggle,
+    pub notification_backends: Vec<NotificationBackend>,
+    pub notification_settings: NotificationSettings,
     pub blink: Toggle,
+    pub alarm: Toggle,
+    pub alarm_settings: AlarmSettings,
+    pub extend_settings: ExtendSettings,
+    pub presets: Presets,
+    pub confirm_reset: Duration,
+    pub quit_running: QuitRunning,
+    pub running_clocks: Option<RunningClocks>,
+    pub scheduled_start: Option<ScheduledStart>,
+    pub start: bool,
+    pub autosave: Duration,
+    pub notice: Option<String>,
+    pub volume: u8,
+    pub sound_device: Option<String>,
+    pub sound_tone: Tone,
+    pub tick_sound: Vec<NotificationKind>,
+    pub countdown_beep: Vec<NotificationKind>,
+    pub urgency: Toggle,
+    pub urgency_thresholds: UrgencyThresholds,
+    pub terminal_title: Toggle,
+    pub terminal_progress: Toggle,
     pub show_menu: bool,
+    pub zen: bool,
+    pub kiosk: bool,
     pub vim_motions: bool,
     pub app_time_format: AppTimeFormat,
     pub content: Content,
@@ -85,7 +211,9 @@ pub struct AppArgs {
     pub event: Event,
     pub app_tx: events::AppEventTx,
     #[cfg(feature = "sound")]
-    pub sound_path: Option<PathBuf>,
+    pub sound_paths: SoundPaths,
+    #[cfg(feature = "sound")]
+    pub pre_alert: Option<Duration>,
     pub footer_toggle_app_time: Toggle,
 }
 
@@ -93,51 +221,202 @@ pub struct FromAppArgs {
     pub args: Args,
     pub stg: AppStorage,
     pub app_tx: events::AppEventTx,
+    pub notice: Option<String>,
+}
+
+fn append_notice(notice: Option<String>, msg: String) -> Option<String> {
+    Some(match notice {
+        Some(notice) => format!("{notice} {msg}"),
+        None => msg,
+    })
 }
 
 /// Creates an `App` by merging `Args` and `AppStorage` (`Args` wins)
 /// and adding `AppEventTx`
 impl From<FromAppArgs> for App {
     fn from(args: FromAppArgs) -> Self {
-        let FromAppArgs { args, stg, app_tx } = args;
+        let FromAppArgs {
+            mut args,
+            stg,
+            app_tx,
+            mut notice,
+        } = args;
+
+        let mut presets = stg.presets;
+        for (name, value) in args.add_preset.drain(..) {
+            presets.set(name, value);
+        }
+        // durations of a preset are handled like durations set by args
+        if let Some(name) = &args.preset {
+            match presets.get(name).map(|preset| preset.value) {
+                Some(PresetValue::Countdown(value)) => {
+                    args.countdown = args.countdown.or(Some(value));
+                }
+                Some(PresetValue::Pomodoro { work, pause }) => {
+                    args.work = args.work.or(Some(work));
+                    args.pause = args.pause.or(Some(pause));
+                }
+                None => {
+                    notice = append_notice(notice, format!("Unknown preset '{name}'."));
+                }
+            }
+        }
+
+        // countdown to a wall-clock time
+        if let Some(until) = args.until {
+            match until.duration_from(AppTime::new().into()) {
+                Ok(value) => args.countdown = Some(value),
+                Err(err) => notice = append_notice(notice, err.to_string()),
+            }
+        }
+
+        // Check args to set a possible mode to start with.
+        let content = match args.mode {
+            Some(mode) => mode,
+            // check other args (especially durations)
+            None => {
+                if args.work.is_some() || args.pause.is_some() {
+                    Content::Pomodoro
+                } else if args.countdown.is_some() {
+                    Content::Countdown
+                } else if args.event.is_some() {
+                    Content::Event
+                }
+                // in other case just use latest stored state
+                else {
+                    stg.content
+                }
+            }
+        };
+
+        // a start scheduled by args replaces a stored one
+        let delay = match (args.start_at, args.start_in) {
+            (Some(start_at), _) => Some(start_at.duration_from(AppTime::new().into())),
+            (_, Some(start_in)) => Some(Ok(start_in)),
+            (None, None) => None,
+        };
+        let scheduled_start = match delay {
+            Some(delay) => match delay.and_then(|delay| {
+                ScheduledStart::new(content, SystemTime::now() + delay, args.pre_start)
+            }) {
+                Ok(start) => Some(start),
+                Err(err) => {
+                    notice = append_notice(notice, err.to_string());
+                    None
+                }
+            },
+            // ignore invalid data of a stored one
+            None => stg
+                .scheduled_start
+                .filter(|start| ScheduledStart::is_supported(start.content)),
+        };
 
         App::new(AppArgs {
             with_decis: args.decis || stg.with_decis,
             show_menu: args.menu || stg.show_menu,
+            // kiosk mode is zen mode, too
+            zen: args.zen || args.kiosk,
+            kiosk: args.kiosk,
             vim_motions: args.vim.unwrap_or(stg.vim).into(),
             notification: args.notification.unwrap_or(stg.notification),
-            blink: args.blink.unwrap_or(stg.blink),
-            app_time_format: stg.app_time_format,
-            // Check args to set a possible mode to start with.
-            content: match args.mode {
-                Some(mode) => mode,
-                // check other args (especially durations)
-                None => {
-                    if args.work.is_some() || args.pause.is_some() {
-                        Content::Pomodoro
-                    } else if args.countdown.is_some() {
-                        Content::Countdown
-                    } else if args.event.is_some() {
-                        Content::Event
-                    }
-                    // in other case just use latest stored state
-                    else {
-                        stg.content
-                    }
+            notification_backends: args
+                .notification_backend
+                .unwrap_or(stg.notification_backends),
+            notification_settings: {
+                let mut settings = stg.notification_settings;
+                for (kind, template) in args.notification_template {
+                    settings.templates.set(kind, template);
                 }
+                settings.urgency = args.notification_urgency.unwrap_or(settings.urgency);
+                settings.timeout = args.notification_timeout.or(settings.timeout);
+                // empty values restore defaults
+                settings.icon = args
+                    .notification_icon
+                    .or(settings.icon)
+                    .filter(|v| !v.is_empty());
+                settings.app_name = args
+                    .notification_app_name
+                    .or(settings.app_name)
+                    .filter(|v| !v.is_empty());
+                settings
+            },
+            blink: args.blink.unwrap_or(stg.blink),
+            alarm: args.alarm.unwrap_or(stg.alarm),
+            #[cfg(feature = "sound")]
+            volume: args.volume.unwrap_or(stg.volume),
+            #[cfg(not(feature = "sound"))]
+            volume: stg.volume,
+            // empty value restores default device
+            #[cfg(feature = "sound")]
+            sound_device: args
+                .sound_device
+                .or(stg.sound_device)
+                .filter(|v| !v.is_empty()),
+            #[cfg(not(feature = "sound"))]
+            sound_device: stg.sound_device,
+            #[cfg(feature = "sound")]
+            sound_tone: args.sound_tone.unwrap_or(stg.sound_tone),
+            #[cfg(not(feature = "sound"))]
+            sound_tone: stg.sound_tone,
+            #[cfg(feature = "sound")]
+            tick_sound: args.tick_sound.unwrap_or(stg.tick_sound),
+            #[cfg(not(feature = "sound"))]
+            tick_sound: stg.tick_sound,
+            #[cfg(feature = "sound")]
+            countdown_beep: args.countdown_beep.unwrap_or(stg.countdown_beep),
+            #[cfg(not(feature = "sound"))]
+            countdown_beep: stg.countdown_beep,
+            alarm_settings: AlarmSettings {
+                interval: args.alarm_interval.unwrap_or(stg.alarm_settings.interval),
+                max_duration: args.alarm_max.unwrap_or(stg.alarm_settings.max_duration),
+                #[cfg(feature = "sound")]
+                volume_ramp: args
+                    .alarm_volume_ramp
+                    .unwrap_or(stg.alarm_settings.volume_ramp),
+                #[cfg(not(feature = "sound"))]
+                volume_ramp: stg.alarm_settings.volume_ramp,
+            },
+            extend_settings: ExtendSettings {
+                extend: args.extend.unwrap_or(stg.extend_settings.extend),
+                extend_more: args.extend_more.unwrap_or(stg.extend_settings.extend_more),
+                snooze: args.snooze.unwrap_or(stg.extend_settings.snooze),
+            },
+            presets,
+            confirm_reset: args.confirm_reset.unwrap_or(stg.confirm_reset),
+            quit_running: args.quit_running.unwrap_or(stg.quit_running),
+            // clocks set by arguments start from scratch
+            running_clocks: stg.running_clocks.map(|clocks| RunningClocks {
+                countdown: clocks.countdown && args.countdown.is_none(),
+                pomodoro: clocks.pomodoro && args.work.is_none() && args.pause.is_none(),
+                ..clocks
+            }),
+            scheduled_start,
+            start: args.start,
+            autosave: args.autosave.unwrap_or(stg.autosave),
+            notice,
+            urgency: args.urgency.unwrap_or(stg.urgency),
+            urgency_thresholds: UrgencyThresholds {
+                warn: args.urgency_warn.unwrap_or(stg.urgency_thresholds.warn),
+                critical: args
+                    .urgency_critical
+                    .unwrap_or(stg.urgency_thresholds.critical),
             },
+            terminal_title: args.terminal_title.unwrap_or(stg.terminal_title),
+            terminal_progress: args.terminal_progress.unwrap_or(stg.terminal_progress),
+            app_time_format: stg.app_time_format,
+            content,
             style: args.style.unwrap_or(stg.style),
             pomodoro_mode: stg.pomodoro_mode,
             pomodoro_round: stg.pomodoro_count,
-            initial_value_work: args.work.unwrap_or(stg.inital_value_work),
+            initial_value_work: args.work.unwrap_or(stg.initial_value_work),
             // invalidate `current_value_work` if an initial value is set via args
             current_value_work: args.work.unwrap_or(stg.current_value_work),
-            initial_value_pause: args.pause.unwrap_or(stg.inital_value_pause),
+            initial_value_pause: args.pause.unwrap_or(stg.initial_value_pause),
             // invalidate `current_value_pause` if an initial value is set via args
             current_value_pause: args.pause.unwrap_or(stg.current_value_pause),
-            initial_value_countdown: args.countdown.unwrap_or(stg.inital_value_countdown),
+            initial_value_countdown: args.countdown.unwrap_or(stg.initial_value_countdown),
             // invalidate `current_value_countdown` if an initial value is set via args
-            current_value_countdown: args.countdown.unwrap_or(stg.inital_value_countdown),
+            current_value_countdown: args.countdown.unwrap_or(stg.current_value_countdown),
             elapsed_value_countdown: match args.countdown {
                 // reset value if countdown is set by arguments
                 Some(_) => Duration::ZERO,
@@ -147,7 +426,16 @@ impl From<FromAppArgs> for App {
             event: args.event.unwrap_or(stg.event),
             app_tx,
             #[cfg(feature = "sound")]
-            sound_path: args.sound,
+            sound_paths: SoundPaths {
+                default: args.sound,
+                countdown_done: args.sound_countdown,
+                work_done: args.sound_work,
+                pause_done: args.sound_pause,
+                event_done: args.sound_event,
+                pre_alert: args.sound_pre_alert,
+            },
+            #[cfg(feature = "sound")]
+            pre_alert: args.pre_alert,
             footer_toggle_app_time: stg.footer_app_time,
         })
     }
@@ -158,6 +446,8 @@ impl App {
         let AppArgs {
             style,
             show_menu,
+            zen,
+            kiosk,
             vim_motions,
             app_time_format,
             initial_value_work,
@@ -174,23 +464,94 @@ impl App {
             pomodoro_round,
             event,
             notification,
+            notification_backends,
+            notification_settings,
             blink,
+            alarm,
+            alarm_settings,
+            extend_settings,
+            presets,
+            confirm_reset,
+            quit_running,
+            running_clocks,
+            scheduled_start,
+            start,
+            autosave,
+            notice,
+            volume,
+            sound_device,
+            tick_sound,
+            countdown_beep,
+            urgency,
+            urgency_thresholds,
+            terminal_title,
+            terminal_progress,
             app_tx,
             footer_toggle_app_time,
             #[cfg(feature = "sound")]
-            sound_path,
+            sound_paths,
+            sound_tone,
+            #[cfg(feature = "sound")]
+            pre_alert,
         } = args;
         let app_time = AppTime::new();
 
+        // without any sound file `sound_tone` is played
         #[cfg(feature = "sound")]
-        let sound = sound_path.and_then(|path| Sound::new(path).ok());
+        let sound = if sound_paths.is_empty()
+            && sound_tone == Tone::Off
+            && tick_sound.is_empty()
+            && countdown_beep.is_empty()
+        {
+            None
+        } else {
+            Sound::new(sound_paths, sound_tone, sound_device.as_deref())
+                .inspect_err(|err| error!("Sound error: {:?}", err))
+                .ok()
+        };
 
-        Self {
+        let mut app = Self {
             mode: Mode::Running,
             notification,
+            notification_backends,
+            notification_settings,
+            flash_count: None,
             blink,
+            alarm,
+            alarm_settings,
+            extend_settings,
+            presets,
+            preset_picker: None,
+            confirm_reset,
+            pending_reset: None,
+            quit_running,
+            pending_quit: false,
+            keep_running: false,
+            scheduled_start,
+            autosave,
+            last_save: Instant::now(),
+            save_requested: false,
+            notice,
+            volume,
+            sound_device,
+            sound_tone,
+            tick_sound,
+            countdown_beep,
+            #[cfg(feature = "sound")]
+            last_clock_second: None,
+            active_alarm: None,
+            urgency,
+            urgency_thresholds,
+            terminal_title,
+            terminal_progress,
+            last_terminal_title: None,
+            last_terminal_progress: None,
             #[cfg(feature = "sound")]
             sound,
+            #[cfg(feature = "sound")]
+            pre_alert,
+            #[cfg(feature = "sound")]
+            pre_alert_played: false,
             content,
             app_time,
             app_time_format,
@@ -211,6 +572,7 @@ impl App {
                 with_decis,
                 app_tx: app_tx.clone(),
                 vim_motions,
+                extend_settings,
             }),
             timer: TimerState::new(
                 ClockState::<clock::Timer>::new(ClockStateArgs {
@@ -233,6 +595,7 @@ impl App {
                 round: pomodoro_round,
                 app_tx: app_tx.clone(),
                 vim_motions,
+                extend_settings,
             }),
             local_time: LocalTimeState::new(LocalTimeStateArgs {
                 app_time,
@@ -254,19 +617,39 @@ impl App {
                 vim_motions,
             ),
             cursor_position: None,
+            zen,
+            kiosk,
+        };
+
+        if let Some(clocks) = running_clocks {
+            app.resume(clocks);
+        }
+
+        if start {
+            let initial_value = app.get_initial_preset_value();
+            app.start_clock(app.content, Duration::ZERO);
+            // remember durations of a clock started from scratch
+            if let Some(value) = initial_value
+                && app.clock_is_running()
+            {
+                app.presets.add_recent(value);
+            }
         }
+
+        app
     }
 
     pub async fn run(
         mut self,
         terminal: &mut Terminal,
         mut events: events::Events,
+        storage: &Storage,
     ) -> Result<Self> {
         // Closure to handle `KeyEvent`'s
         let handle_key_event = |app: &mut Self, key: KeyEvent| {
             debug!("Received key {:?}", key.code);
             match key.code {
-                KeyCode::Char('q') => app.mode = Mode::Quit,
+                KeyCode::Char('q') => app.quit(app.quit_running),
                 KeyCode::Char('1') => app.content = Content::Countdown,
                 KeyCode::Char('2') => app.content = Content::Timer,
                 KeyCode::Char('3') => app.content = Content::Pomodoro,
@@ -331,27 +714,138 @@ impl App {
                 }
                 // toogle menu
                 KeyCode::Char('m') => app.footer.set_show_menu(!app.footer.get_show_menu()),
+                // toogle zen mode
+                KeyCode::Char('z') => app.zen = !app.zen,
+                // open preset picker
+                KeyCode::Char('p')
+                    if matches!(app.content, Content::Countdown | Content::Pomodoro)
+                        && matches!(app.get_edit_mode(), AppEditMode::None) =>
+                {
+                    app.open_preset_picker();
+                }
+                // change volume
+                #[cfg(feature = "sound")]
+                KeyCode::Char('[') => app.volume = app.volume.saturating_sub(VOLUME_STEP),
+                #[cfg(feature = "sound")]
+                KeyCode::Char(']') => app.volume = (app.volume + VOLUME_STEP).min(MAX_VOLUME),
                 _ => {}
             };
         };
         // Closure to handle `TuiEvent`'s
         // It returns a flag (bool) whether the app needs to be re-drawn or not
         let handle_tui_events = |app: &mut Self, event: events::TuiEvent| -> Result<bool> {
+            // Quit without asking, `Storage` is saved afterwards
+            if matches!(event, events::TuiEvent::Terminate) {
+                app.quit(match app.quit_running {
+                    QuitRunning::Keep => QuitRunning::Keep,
+                    QuitRunning::Ask | QuitRunning::Pause => QuitRunning::Pause,
+                });
+                return Ok(false);
+            }
+
+            // An active alarm, a notice or kiosk mode take keys first
+            if let events::TuiEvent::Crossterm(CrosstermEvent::Key(key)) = event {
+                match first_key_action(
+                    &key,
+                    app.active_alarm.is_some(),
+                    app.notice.is_some(),
+                    app.kiosk,
+                ) {
+                    FirstKeyAction::AcknowledgeAlarm => {
+                        app.active_alarm = None;
+                        return Ok(true);
+                    }
+                    FirstKeyAction::CloseNotice => {
+                        app.notice = None;
+                        return Ok(true);
+                    }
+                    FirstKeyAction::UnlockKiosk => {
+                        app.kiosk = false;
+                        app.zen = false;
+                        return Ok(true);
+                    }
+                    FirstKeyAction::Ignore => return Ok(false),
+                    FirstKeyAction::None => {}
+                }
+            }
+
             if matches!(event, events::TuiEvent::Tick) {
+                app.tick_alarm();
                 app.app_time = AppTime::new();
                 app.countdown.set_app_time(app.app_time);
                 app.local_time.set_app_time(app.app_time);
                 app.event.set_app_time(app.app_time);
+                app.flash_count = clock::count_clock_done(app.flash_count);
+                app.check_scheduled_start();
+            }
+
+            // Any key answers a pending quit, but `k` or `p` confirm it only
+            if app.pending_quit
+                && let events::TuiEvent::Crossterm(CrosstermEvent::Key(key)) = event
+            {
+                app.pending_quit = false;
+                match key.code {
+                    KeyCode::Char('k') => app.quit(QuitRunning::Keep),
+                    KeyCode::Char('p') => app.quit(QuitRunning::Pause),
+                    _ => {}
+                }
+                return Ok(true);
+            }
+
+            // Any key answers a pending reset, but `y` confirms it only
+            if app.pending_reset.is_some()
+                && let events::TuiEvent::Crossterm(CrosstermEvent::Key(key)) = event
+            {
+                if let Some(reset) = app.pending_reset.take()
+                    && key.code == KeyCode::Char('y')
+                {
+                    app.update_content(reset);
+                }
+                return Ok(true);
+            }
+
+            if let Some(picker) = &mut app.preset_picker
+                && let events::TuiEvent::Crossterm(CrosstermEvent::Key(key)) = event
+            {
+                match key.code {
+                    KeyCode::Up => picker.prev(),
+                    KeyCode::Char('k') if app.vim_motions => picker.prev(),
+                    KeyCode::Down => picker.next(),
+                    KeyCode::Char('j') if app.vim_motions => picker.next(),
+                    KeyCode::Enter => {
+                        if let Some(value) = picker.get_selected() {
+                            app.apply_preset(value);
+                        }
+                        app.preset_picker = None;
+                    }
+                    KeyCode::Esc | KeyCode::Char('p') => app.preset_picker = None,
+                    _ => {}
+                }
+                return Ok(true);
+            }
+
+            if let events::TuiEvent::Crossterm(CrosstermEvent::Key(key)) = event
+                && app.needs_reset_confirmation(&key)
+            {
+                app.pending_reset = Some(event);
+                return Ok(true);
             }
 
             // Pipe events into subviews and handle only 'unhandled' events afterwards
-            let unhandled = match app.content {
-                Content::Countdown => app.countdown.update(event.clone()),
-                Content::Timer => app.timer.update(event.clone()),
-                Content::Pomodoro => app.pomodoro.update(event.clone()),
-                Content::Event => app.event.update(event.clone()),
-                Content::LocalTime => app.local_time.update(event.clone()),
-            };
+            let initial_value = app.get_initial_preset_value();
+            let unhandled = app.update_content(event.clone());
+            // remember durations of a clock started from scratch
+            if let Some(value) = initial_value
+                && app.clock_is_running()
+            {
+                app.presets.add_recent(value);
+            }
+            #[cfg(feature = "sound")]
+            if matches!(event, events::TuiEvent::Tick) {
+                app.check_pre_alert();
+                app.check_clock_sounds();
+            }
+
             // from all 'unhandled' events we are interested in `CrosstermEvent::Key` only
             if let Some(events::TuiEvent::Crossterm(CrosstermEvent::Key(key))) = unhandled {
                 handle_key_event(app, key);
@@ -375,27 +869,17 @@ impl App {
                 events::AppEvent::ClockDone(type_id, name) => {
                     debug!("AppEvent::ClockDone");
 
-                    if app.notification == Toggle::On {
-                        let msg = match type_id {
-                            ClockTypeId::Timer => {
-                                format!("{name} stopped by reaching its maximum value.")
-                            }
-                            _ => format!("{type_id:?} {name} done!"),
-                        };
-                        // notification
-                        let result = notify_rust::Notification::new()
-                            .summary(&msg.to_uppercase())
-                            .show();
-                        if let Err(err) = result {
-                            error!("on_done {name} error: {err}");
-                        }
-                    };
+                    let kind = get_notification_kind(&type_id, &name);
+                    let ctx = app.get_notification_context(kind, name);
+                    let msg = app.notification_settings.templates.message(kind, &ctx);
 
+                    app.notify(&msg);
+                    app.save_requested = true;
                     #[cfg(feature = "sound")]
-                    if let Some(sound) = &app.sound {
-                        if let Err(err) = sound.play() {
-                            error!("Sound error: {:?}", err);
-                        }
+                    app.play_sound(kind.into(), 1.0);
+
+                    if app.alarm == Toggle::On {
+                        app.active_alarm = Some(Alarm::new(kind, msg));
                     }
                 }
                 events::AppEvent::SetCursor(position) => {
@@ -407,13 +891,23 @@ impl App {
             Ok(trigger_redraw)
         };
 
+        // keep initial state to save it in case of a panic (updated after each event)
+        storage::set_latest(self.to_storage());
         while self.is_running() {
             if let Some(event) = events.next().await {
+                let is_tick = matches!(event, events::Event::Terminal(events::TuiEvent::Tick));
                 match event {
                     events::Event::Terminal(e) => {
+                        // keys might change data to save
+                        let save_state =
+                            matches!(e, events::TuiEvent::Crossterm(CrosstermEvent::Key(_)))
+                                .then(|| self.save_state());
                         if let Ok(true) = handle_tui_events(&mut self, e) {
                             self.draw(terminal)?;
                         }
+                        if save_state.is_some_and(|state| state != self.save_state()) {
+                            self.save_requested = true;
+                        }
                     }
                     events::Event::App(e) => {
                         if let Ok(true) = handle_app_events(&mut self, e) {
@@ -421,6 +915,11 @@ impl App {
                         }
                     }
                 };
+                if is_tick {
+                    self.autosave(storage);
+                }
+                // latest state to save in case of a panic
+                storage::set_latest(self.to_storage());
             }
         }
         Ok(self)
@@ -430,6 +929,202 @@ impl App {
         self.mode != Mode::Quit
     }
 
+    fn save_state(&self) -> SaveState {
+        SaveState {
+            content: self.content,
+            style: self.style,
+            with_decis: self.with_decis,
+            show_menu: self.footer.get_show_menu(),
+            footer_app_time: *self.footer.app_time_format(),
+            app_time_format: self.app_time_format,
+            volume: self.volume,
+            presets: self.presets.clone(),
+            countdown: self.countdown.snapshot(),
+            timer: self.timer.get_clock().snapshot(),
+            pomodoro: self.pomodoro.snapshot(),
+            event: self.event.get_event(),
+        }
+    }
+
+    /// Saves data periodically or if it has been requested (e.g. after significant changes).
+    fn autosave(&mut self, storage: &Storage) {
+        let is_due = !self.autosave.is_zero() && self.last_save.elapsed() >= self.autosave;
+        if !self.save_requested && !is_due {
+            return;
+        }
+        if !self.autosave.is_zero()
+            && let Err(err) = storage.save(self.to_storage())
+        {
+            error!("Autosave failed: {:?}", err);
+        }
+        self.last_save = Instant::now();
+        self.save_requested = false;
+    }
+
+    /// Quits the app. Running clocks are handled by `quit_running`.
+    fn quit(&mut self, quit_running: QuitRunning) {
+        if !self.any_clock_is_running() {
+            self.mode = Mode::Quit;
+            return;
+        }
+        match quit_running {
+            QuitRunning::Ask => self.pending_quit = true,
+            QuitRunning::Keep => {
+                self.keep_running = true;
+                self.mode = Mode::Quit;
+            }
+            QuitRunning::Pause => self.mode = Mode::Quit,
+        }
+    }
+
+    /// Checks all clocks (not only the one of current `content`)
+    fn any_clock_is_running(&self) -> bool {
+        self.countdown.is_running()
+            || self.timer.get_clock().is_running()
+            || self.pomodoro.get_clock().is_running()
+    }
+
+    /// Continues clocks kept running while the app was closed
+    fn resume(&mut self, clocks: RunningClocks) {
+        let duration = SystemTime::now()
+            .duration_since(clocks.since)
+            .unwrap_or_default();
+        if clocks.countdown {
+            self.countdown.resume(duration);
+        }
+        if clocks.timer {
+            self.timer.resume(duration);
+        }
+        if clocks.pomodoro {
+            self.pomodoro.resume(duration);
+        }
+    }
+
+    /// Starts the clock of `content`, which should have been started `ago`.
+    /// Finished clocks start again, running or edited clocks are not changed.
+    /// Event and local time clocks are running always.
+    fn start_clock(&mut self, content: Content, ago: Duration) {
+        match content {
+            Content::Countdown => self.countdown.start(ago),
+            Content::Timer => self.timer.start(ago),
+            Content::Pomodoro => self.pomodoro.start(ago),
+            Content::Event | Content::LocalTime => {}
+        }
+    }
+
+    /// Starts a scheduled clock as soon as it's due and sends its pre-start notification.
+    /// Starting the clock manually before disarms it.
+    fn check_scheduled_start(&mut self) {
+        let Some(start) = &mut self.scheduled_start else {
+            return;
+        };
+        let now = OffsetDateTime::from(self.app_time);
+        let (is_running, is_edit_mode) = match start.content {
+            Content::Countdown => (
+                self.countdown.is_running(),
+                self.countdown.is_clock_edit_mode() || self.countdown.is_time_edit_mode(),
+            ),
+            Content::Pomodoro => {
+                let clock = self.pomodoro.get_clock();
+                (clock.is_running(), clock.is_edit_mode())
+            }
+            Content::Timer | Content::Event | Content::LocalTime => {
+                unreachable!("checked by `ScheduledStart::new`")
+            }
+        };
+        if is_running {
+            self.scheduled_start = None;
+            self.save_requested = true;
+        } else if start.is_due(now) {
+            // wait for an edit to be finished
+            if is_edit_mode {
+                return;
+            }
+            // count time passed since it's been due (e.g. while the app was closed).
+            // A finished clock is reset to its initial value before.
+            let (content, overdue) = (start.content, start.overdue(now));
+            self.start_clock(content, overdue);
+            self.scheduled_start = None;
+            self.save_requested = true;
+        } else if start.needs_pre_start_notification(now) {
+            start.pre_start_notified = true;
+            let msg = format!("Scheduled {}.", start.message(now));
+            self.notify(&msg);
+            self.save_requested = true;
+        }
+    }
+
+    fn open_preset_picker(&mut self) {
+        let items = self
+            .presets
+            .items(self.content)
+            .into_iter()
+            .map(|(name, value)| PresetItem {
+                name: name.map(str::to_owned),
+                value,
+            })
+            .collect();
+        self.preset_picker = Some(PresetPickerState::new(items));
+    }
+
+    fn apply_preset(&mut self, value: PresetValue) {
+        match value {
+            PresetValue::Countdown(value) => self.countdown.apply_preset(value),
+            PresetValue::Pomodoro { work, pause } => self.pomodoro.apply_preset(work, pause),
+        }
+    }
+
+    /// Durations of current countdown or pomodoro clock, but only if it's in its initial state
+    fn get_initial_preset_value(&self) -> Option<PresetValue> {
+        match self.content {
+            Content::Countdown if self.countdown.get_clock().is_initial() => Some(
+                PresetValue::Countdown((*self.countdown.get_clock().get_initial_value()).into()),
+            ),
+            Content::Pomodoro if self.pomodoro.get_clock().is_initial() => {
+                Some(PresetValue::Pomodoro {
+                    work: (*self.pomodoro.get_clock_work().get_initial_value()).into(),
+                    pause: (*self.pomodoro.get_clock_pause().get_initial_value()).into(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Pipes an event into current content
+    fn update_content(&mut self, event: events::TuiEvent) -> Option<events::TuiEvent> {
+        match self.content {
+            Content::Countdown => self.countdown.update(event),
+            Content::Timer => self.timer.update(event),
+            Content::Pomodoro => self.pomodoro.update(event),
+            Content::Event => self.event.update(event),
+            Content::LocalTime => self.local_time.update(event),
+        }
+    }
+
+    /// Checks whether a reset by `key` has to be confirmed,
+    /// because the clock(s) to reset have been running longer than `confirm_reset`
+    fn needs_reset_confirmation(&self, key: &KeyEvent) -> bool {
+        if self.confirm_reset.is_zero()
+            || key.code != KeyCode::Char('r')
+            || !matches!(self.get_edit_mode(), AppEditMode::None)
+        {
+            return false;
+        }
+        let run_duration = match self.content {
+            Content::Countdown => self.countdown.get_run_duration(),
+            Content::Timer => self.timer.get_clock().get_run_duration(),
+            // reset of all clocks
+            Content::Pomodoro if key.modifiers.contains(KeyModifiers::CONTROL) => self
+                .pomodoro
+                .get_clock_work()
+                .get_run_duration()
+                .saturating_add(self.pomodoro.get_clock_pause().get_run_duration()),
+            Content::Pomodoro => self.pomodoro.get_clock().get_run_duration(),
+            Content::Event | Content::LocalTime => return false,
+        };
+        run_duration > self.confirm_reset
+    }
+
     fn get_edit_mode(&self) -> AppEditMode {
         match self.content {
             Content::Countdown => {
@@ -489,6 +1184,88 @@ impl App {
         }
     }
 
+    /// Color of the current clock depending on its `UrgencyLevel`.
+    /// Countdown and Pomodoro clocks are supported only.
+    fn get_urgency_color(&self) -> Option<Color> {
+        if self.urgency == Toggle::Off || !matches!(self.get_edit_mode(), AppEditMode::None) {
+            return None;
+        }
+
+        let clock = match self.content {
+            Content::Countdown => self.countdown.get_clock(),
+            Content::Pomodoro => self.pomodoro.get_clock(),
+            _ => return None,
+        };
+
+        let level = self.urgency_thresholds.get_level(
+            clock.get_percentage_done(),
+            Duration::from(*clock.get_current_value()),
+        );
+        Some(level.color())
+    }
+
+    /// Title of terminal window or tab, e.g. "⏵ 12:34 pomodoro work #3"
+    fn get_terminal_title(&self) -> String {
+        let symbol = if self.clock_is_running() {
+            "⏵"
+        } else {
+            "⏸"
+        };
+        match self.content {
+            Content::Countdown => format!(
+                "{symbol} {} countdown",
+                self.countdown.get_clock().get_current_value()
+            ),
+            Content::Timer => format!(
+                "{symbol} {} timer",
+                self.timer.get_clock().get_current_value()
+            ),
+            Content::Pomodoro => format!(
+                "{symbol} {} pomodoro {} #{}",
+                self.pomodoro.get_clock().get_current_value(),
+                self.pomodoro.get_mode().to_string().to_lowercase(),
+                self.pomodoro.get_round()
+            ),
+            Content::Event => format!(
+                "{symbol} {} {}",
+                DurationEx::from(Duration::from(self.event.get_duration())),
+                self.event.get_event().title.unwrap_or("event".into())
+            ),
+            Content::LocalTime => {
+                format!("{} local time", self.app_time.format(&self.app_time_format))
+            }
+        }
+    }
+
+    fn get_terminal_progress(&self) -> Progress {
+        match self.get_percentage_done() {
+            Some(percentage) if self.clock_is_running() => Progress::Normal(percentage),
+            Some(percentage) => Progress::Paused(percentage),
+            None => Progress::None,
+        }
+    }
+
+    /// Sends title and progress to terminal, but only if they have been changed.
+    fn update_terminal_status(&mut self) -> Result<()> {
+        if self.terminal_title == Toggle::On {
+            let title = self.get_terminal_title();
+            if self.last_terminal_title.as_ref() != Some(&title) {
+                terminal::set_title(&title)?;
+                self.last_terminal_title = Some(title);
+            }
+        }
+
+        if self.terminal_progress == Toggle::On {
+            let progress = self.get_terminal_progress();
+            if self.last_terminal_progress != Some(progress) {
+                terminal::set_progress(progress)?;
+                self.last_terminal_progress = Some(progress);
+            }
+        }
+
+        Ok(())
+    }
+
     fn draw(&mut self, terminal: &mut Terminal) -> Result<()> {
         terminal.draw(|frame| {
             frame.render_stateful_widget(AppWidget, frame.area(), self);
@@ -498,30 +1275,218 @@ impl App {
                 frame.set_cursor_position(position);
             }
         })?;
+        self.update_terminal_status()?;
         Ok(())
     }
 
+    /// Sends a notification by all `notification_backends`
+    fn notify(&mut self, msg: &str) {
+        if self.notification == Toggle::Off {
+            return;
+        }
+        for backend in self.notification_backends.clone() {
+            match backend {
+                NotificationBackend::Flash => {
+                    self.flash_count = Some(clock::MAX_DONE_COUNT);
+                }
+                _ => {
+                    if let Err(err) = notification::send(backend, &self.notification_settings, msg)
+                    {
+                        error!("Notification error ({backend:?}): {err}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "sound")]
+    fn play_sound(&self, kind: SoundKind, volume: f32) {
+        let Some(sound) = &self.sound else {
+            return;
+        };
+        if let Err(err) = sound.play(kind, volume * self.volume as f32 / MAX_VOLUME as f32) {
+            error!("Sound error: {:?}", err);
+        }
+    }
+
+    /// Repeats sound and notification of an active alarm
+    fn tick_alarm(&mut self) {
+        let Some(alarm) = &mut self.active_alarm else {
+            return;
+        };
+        match alarm.tick(&self.alarm_settings) {
+            AlarmAction::None => {}
+            AlarmAction::Repeat => {
+                let msg = alarm.message.clone();
+                #[cfg(feature = "sound")]
+                {
+                    let kind = alarm.kind.into();
+                    let volume = alarm.get_volume(&self.alarm_settings);
+                    self.play_sound(kind, volume);
+                }
+                self.notify(&msg);
+            }
+            AlarmAction::Stop => self.active_alarm = None,
+        }
+    }
+
+    /// Plays `SoundKind::Tick` each second of a running clock
+    /// and `SoundKind::CountdownBeep` during the last three seconds of a running countdown.
+    #[cfg(feature = "sound")]
+    fn check_clock_sounds(&mut self) {
+        let (kind, running, current) = match self.content {
+            Content::Countdown => {
+                let clock = self.countdown.get_clock();
+                (
+                    NotificationKind::Countdown,
+                    clock.is_running(),
+                    Duration::from(*clock.get_current_value()),
+                )
+            }
+            Content::Timer => {
+                let clock = self.timer.get_clock();
+                (
+                    NotificationKind::Timer,
+                    clock.is_running(),
+                    Duration::from(*clock.get_current_value()),
+                )
+            }
+            Content::Pomodoro => {
+                let clock = self.pomodoro.get_clock();
+                let kind = match self.pomodoro.get_mode() {
+                    PomodoroMode::Work => NotificationKind::Work,
+                    PomodoroMode::Pause => NotificationKind::Pause,
+                };
+                (
+                    kind,
+                    clock.is_running(),
+                    Duration::from(*clock.get_current_value()),
+                )
+            }
+            _ => (NotificationKind::Event, false, Duration::ZERO),
+        };
+
+        if !running {
+            self.last_clock_second = None;
+            return;
+        }
+
+        let second = current.as_secs();
+        // play sounds only if a second has been changed (but not at start)
+        let changed = self.last_clock_second.is_some_and(|last| last != second);
+        self.last_clock_second = Some(second);
+        if !changed {
+            return;
+        }
+
+        let is_countdown = kind != NotificationKind::Timer;
+        // a finished countdown plays its own sound
+        if is_countdown && second == 0 {
+            return;
+        }
+        if is_countdown && (1..=3).contains(&second) && self.countdown_beep.contains(&kind) {
+            self.play_sound(SoundKind::CountdownBeep, 1.0);
+        } else if self.tick_sound.contains(&kind) {
+            self.play_sound(SoundKind::Tick, 1.0);
+        }
+    }
+
+    /// Plays `SoundKind::PreAlert` once, if remaining time of a running countdown (or pomodoro) clock reaches `pre_alert`
+    #[cfg(feature = "sound")]
+    fn check_pre_alert(&mut self) {
+        let Some(pre_alert) = self.pre_alert else {
+            return;
+        };
+        let clock = match self.content {
+            Content::Countdown => self.countdown.get_clock(),
+            Content::Pomodoro => self.pomodoro.get_clock(),
+            _ => return,
+        };
+        let remaining = Duration::from(*clock.get_current_value());
+        if remaining > pre_alert {
+            // reset flag to play it again next time
+            self.pre_alert_played = false;
+        } else if clock.is_running() && !remaining.is_zero() && !self.pre_alert_played {
+            self.pre_alert_played = true;
+            self.play_sound(SoundKind::PreAlert, 1.0);
+        }
+    }
+
+    fn get_notification_context(
+        &self,
+        kind: NotificationKind,
+        name: String,
+    ) -> NotificationContext {
+        let duration = match kind {
+            NotificationKind::Countdown => Some(*self.countdown.get_clock().get_initial_value()),
+            NotificationKind::Timer => Some(*self.timer.get_clock().get_current_value()),
+            NotificationKind::Work => Some(*self.pomodoro.get_clock_work().get_initial_value()),
+            NotificationKind::Pause => Some(*self.pomodoro.get_clock_pause().get_initial_value()),
+            NotificationKind::Event => None,
+        };
+        let next = match kind {
+            NotificationKind::Work => PomodoroMode::Pause.to_string(),
+            NotificationKind::Pause => PomodoroMode::Work.to_string(),
+            _ => String::new(),
+        };
+        NotificationContext {
+            name,
+            duration: duration.map(|d| d.to_string()).unwrap_or_default(),
+            round: self.pomodoro.get_round().to_string(),
+            time: self.app_time.format(&self.app_time_format),
+            next,
+        }
+    }
+
     pub fn to_storage(&self) -> AppStorage {
         AppStorage {
+            version: storage::VERSION,
             content: self.content,
             show_menu: self.footer.get_show_menu(),
             vim: self.vim_motions.into(),
             notification: self.notification,
+            notification_backends: self.notification_backends.clone(),
+            notification_settings: self.notification_settings.clone(),
             blink: self.blink,
+            alarm: self.alarm,
+            alarm_settings: self.alarm_settings,
+            extend_settings: self.extend_settings,
+            presets: self.presets.clone(),
+            confirm_reset: self.confirm_reset,
+            quit_running: self.quit_running,
+            autosave: self.autosave,
+            scheduled_start: self.scheduled_start.clone(),
+            running_clocks: self.keep_running.then(|| RunningClocks {
+                since: SystemTime::now(),
+                countdown: self.countdown.is_running(),
+                timer: self.timer.get_clock().is_running(),
+                pomodoro: self.pomodoro.get_clock().is_running(),
+            }),
+            volume: self.volume,
+            sound_device: self.sound_device.clone(),
+            sound_tone: self.sound_tone,
+            tick_sound: self.tick_sound.clone(),
+            countdown_beep: self.countdown_beep.clone(),
+            urgency: self.urgency,
+            urgency_thresholds: self.urgency_thresholds,
+            terminal_title: self.terminal_title,
+            terminal_progress: self.terminal_progress,
             app_time_format: self.app_time_format,
             style: self.style,
             with_decis: self.with_decis,
             pomodoro_mode: self.pomodoro.get_mode().clone(),
             pomodoro_count: self.pomodoro.get_round(),
-            inital_value_work: Duration::from(*self.pomodoro.get_clock_work().get_initial_value()),
+            initial_value_work: Duration::from(*self.pomodoro.get_clock_work().get_initial_value()),
             current_value_work: Duration::from(*self.pomodoro.get_clock_work().get_current_value()),
-            inital_value_pause: Duration::from(
+            initial_value_pause: Duration::from(
                 *self.pomodoro.get_clock_pause().get_initial_value(),
             ),
             current_value_pause: Duration::from(
                 *self.pomodoro.get_clock_pause().get_current_value(),
             ),
-            inital_value_countdown: Duration::from(*self.countdown.get_clock().get_initial_value()),
+            initial_value_countdown: Duration::from(
+                *self.countdown.get_clock().get_initial_value(),
+            ),
             current_value_countdown: Duration::from(
                 *self.countdown.get_clock().get_current_value(),
             ),
@@ -533,10 +1498,77 @@ impl App {
     }
 }
 
+#[cfg(feature = "sound")]
+impl From<NotificationKind> for SoundKind {
+    fn from(kind: NotificationKind) -> Self {
+        match kind {
+            NotificationKind::Countdown => SoundKind::CountdownDone,
+            NotificationKind::Work => SoundKind::WorkDone,
+            NotificationKind::Pause => SoundKind::PauseDone,
+            NotificationKind::Event => SoundKind::EventDone,
+            NotificationKind::Timer => SoundKind::Default,
+        }
+    }
+}
+
+/// Pomodoro clocks are countdowns named by their `PomodoroMode` (`Work` or `Pause`)
+fn get_notification_kind(type_id: &ClockTypeId, name: &str) -> NotificationKind {
+    match type_id {
+        ClockTypeId::Countdown if name == PomodoroMode::Work.to_string() => NotificationKind::Work,
+        ClockTypeId::Countdown if name == PomodoroMode::Pause.to_string() => {
+            NotificationKind::Pause
+        }
+        ClockTypeId::Countdown => NotificationKind::Countdown,
+        ClockTypeId::Timer => NotificationKind::Timer,
+        ClockTypeId::Event => NotificationKind::Event,
+    }
+}
+
+/// Key to unlock kiosk mode: `ctrl+u`
+fn is_kiosk_unlock_key(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('u') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Action of a key taken before any other key handling (see `first_key_action`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FirstKeyAction {
+    AcknowledgeAlarm,
+    CloseNotice,
+    UnlockKiosk,
+    /// Key is ignored (kiosk mode)
+    Ignore,
+    /// Key is handled as usual
+    None,
+}
+
+/// Any key acknowledges an active alarm first, then it closes a notice.
+/// In kiosk mode all other keys are ignored, except the one to unlock it.
+/// An alarm is acknowledged by the unlock key only to keep kiosk mode read-only.
+fn first_key_action(
+    key: &KeyEvent,
+    active_alarm: bool,
+    notice: bool,
+    kiosk: bool,
+) -> FirstKeyAction {
+    let unlock_key = is_kiosk_unlock_key(key);
+    if active_alarm && (!kiosk || unlock_key) {
+        FirstKeyAction::AcknowledgeAlarm
+    } else if notice {
+        FirstKeyAction::CloseNotice
+    } else if kiosk && unlock_key {
+        FirstKeyAction::UnlockKiosk
+    } else if kiosk {
+        FirstKeyAction::Ignore
+    } else {
+        FirstKeyAction::None
+    }
+}
+
 struct AppWidget;
 
 impl AppWidget {
     fn render_content(&self, area: Rect, buf: &mut Buffer, state: &mut App) {
+        let urgency_color = state.get_urgency_color();
         match state.content {
             Content::Timer => {
                 Timer {
@@ -548,11 +1580,13 @@ impl AppWidget {
             Content::Countdown => Countdown {
                 style: state.style,
                 blink: state.blink == Toggle::On,
+                urgency_color,
             }
             .render(area, buf, &mut state.countdown),
             Content::Pomodoro => PomodoroWidget {
                 style: state.style,
                 blink: state.blink == Toggle::On,
+                urgency_color,
             }
             .render(area, buf, &mut state.pomodoro),
             Content::Event => EventWidget {
@@ -565,11 +1599,8 @@ impl AppWidget {
             }
         };
     }
-}
 
-impl StatefulWidget for AppWidget {
-    type State = App;
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+    fn render_all(&self, area: Rect, buf: &mut Buffer, state: &mut App) {
         let [v0, v1, v2] = Layout::vertical([
             Constraint::Length(1),
             Constraint::Percentage(100),
@@ -580,6 +1611,11 @@ impl StatefulWidget for AppWidget {
         // header
         Header {
             percentage: state.get_percentage_done(),
+            color: state.get_urgency_color(),
+            label: state
+                .scheduled_start
+                .as_ref()
+                .map(|start| format!("armed: {}", start.message(state.app_time.into()))),
         }
         .render(v0, buf);
         // content
@@ -594,3 +1630,112 @@ impl StatefulWidget for AppWidget {
         .render(v2, buf, &mut state.footer);
     }
 }
+
+impl StatefulWidget for AppWidget {
+    type State = App;
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // zen mode: content only
+        if state.zen {
+            self.render_content(area, buf, state);
+        } else {
+            self.render_all(area, buf, state);
+        }
+
+        if let Some(notice) = &state.notice {
+            ConfirmPopup {
+                title: "notice",
+                message: notice,
+                options: &[("any key", "close")],
+            }
+            .render(area, buf);
+        }
+
+        if let Some(picker) = &mut state.preset_picker {
+            PresetPicker.render(area, buf, picker);
+        }
+
+        if state.pending_quit {
+            ConfirmPopup {
+                title: "quit",
+                message: "Clocks are still running.",
+                options: &[
+                    ("k", "quit and keep running"),
+                    ("p", "quit and pause"),
+                    ("any other key", "cancel"),
+                ],
+            }
+            .render(area, buf);
+        }
+
+        if state.pending_reset.is_some() {
+            ConfirmPopup {
+                title: "reset",
+                message: "Reset long running clock?",
+                options: &[("y", "reset"), ("any other key", "cancel")],
+            }
+            .render(area, buf);
+        }
+
+        if let Some(alarm) = &state.active_alarm {
+            AlarmBanner {
+                kind: alarm.kind,
+                message: &alarm.message,
+                blink: alarm.should_blink(),
+            }
+            .render(area, buf);
+        }
+
+        // flash screen by inverting all colors
+        if clock::should_blink(state.flash_count) {
+            buf.set_style(area, RatatuiStyle::new().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_first_key_action() {
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        let unlock_key = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::CONTROL);
+        // alarm: any key
+        assert_eq!(
+            first_key_action(&key, true, true, false),
+            FirstKeyAction::AcknowledgeAlarm
+        );
+        // alarm in kiosk mode: unlock key only
+        assert_eq!(
+            first_key_action(&key, true, false, true),
+            FirstKeyAction::Ignore
+        );
+        assert_eq!(
+            first_key_action(&unlock_key, true, false, true),
+            FirstKeyAction::AcknowledgeAlarm
+        );
+        // notice: any key, in kiosk mode, too
+        assert_eq!(
+            first_key_action(&key, false, true, false),
+            FirstKeyAction::CloseNotice
+        );
+        assert_eq!(
+            first_key_action(&key, false, true, true),
+            FirstKeyAction::CloseNotice
+        );
+        // kiosk mode
+        assert_eq!(
+            first_key_action(&unlock_key, false, false, true),
+            FirstKeyAction::UnlockKiosk
+        );
+        assert_eq!(
+            first_key_action(&key, false, false, true),
+            FirstKeyAction::Ignore
+        );
+        assert_eq!(
+            first_key_action(&key, false, false, false),
+            FirstKeyAction::None
+        );
+    }
+}
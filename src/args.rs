@@ -1,11 +1,23 @@
+#[cfg(feature = "sound")]
+use crate::storage::MAX_VOLUME;
 use crate::{
-    common::{Content, Style, Toggle},
+    alarm::parse_alarm_interval,
+    common::{Content, QuitRunning, Style, Toggle},
     duration,
     event::{Event, parse_event},
+    notification::{
+        NotificationBackend, NotificationKind, NotificationUrgency, parse_notification_template,
+    },
+    preset::{PresetValue, parse_preset},
+    storage,
+    urgency::{UrgencyThreshold, parse_urgency_threshold},
+    wall_time::{self, WallTime},
 };
 #[cfg(feature = "sound")]
-use crate::{sound, sound::SoundError};
-use clap::Parser;
+use crate::{common::Tone, sound, sound::SoundError};
+use clap::{Parser, Subcommand};
+#[cfg(feature = "sound")]
+use clap::{ValueEnum, builder::TypedValueParser};
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -14,21 +26,61 @@ pub const LOG_DIRECTORY_DEFAULT_MISSING_VALUE: &str = " "; // empty string
 #[derive(Parser)]
 #[command(version)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     #[arg(long, short, value_parser = duration::parse_long_duration,
-        help = "Countdown time to start from. Formats: 'Yy Dd hh:mm:ss', 'Dd hh:mm:ss', 'Yy mm:ss', 'Dd mm:ss', 'Yy ss', 'Dd ss', 'hh:mm:ss', 'mm:ss', 'ss'. Examples: '1y 5d 10:30:00', '2d 4:00', '1d 10', '5:03'."
+        help = "Countdown time to start from. Formats: 'Yy Dd hh:mm:ss', 'Dd hh:mm:ss', 'Yy mm:ss', 'Dd mm:ss', 'Yy ss', 'Dd ss', 'hh:mm:ss', 'mm:ss', 'ss' or units 'y', 'w', 'd', 'h', 'm', 's' (combinable with time formats). Examples: '1y 5d 10:30:00', '2d 4:00', '1d 10', '5:03', '1h30m', '1.5h', '2w 12h'."
     )]
     pub countdown: Option<Duration>,
 
-    #[arg(long, short, value_parser = duration::parse_duration,
-        help = "Work time to count down from. Formats: 'ss', 'mm:ss', 'hh:mm:ss'"
+    #[arg(long, short, value_parser = duration::parse_long_duration,
+        help = "Work time to count down from, e.g. '25:00'."
     )]
     pub work: Option<Duration>,
 
-    #[arg(long, short, value_parser = duration::parse_duration,
-        help = "Pause time to count down from. Formats: 'ss', 'mm:ss', 'hh:mm:ss'"
+    #[arg(long, short, value_parser = duration::parse_long_duration,
+        help = "Pause time to count down from, e.g. '5:00'."
     )]
     pub pause: Option<Duration>,
 
+    #[arg(
+        long,
+        value_parser = wall_time::parse_wall_time,
+        conflicts_with = "countdown",
+        help = "Local time to count down to. A time of day which has passed already is the one of tomorrow. Formats: 'HH:MM', 'HH:MM:SS', 'YYYY-MM-DD HH:MM', 'YYYY-MM-DD HH:MM:SS'. Examples: '17:30', '2026-10-19 09:00'."
+    )]
+    pub until: Option<WallTime>,
+
+    #[arg(
+        long,
+        value_parser = wall_time::parse_wall_time,
+        conflicts_with = "start_in",
+        help = "Arm countdown or pomodoro to start at a local time, e.g. '14:00' or '2026-10-19 09:00'."
+    )]
+    pub start_at: Option<WallTime>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Arm countdown or pomodoro to start after a delay, e.g. '10m'."
+    )]
+    pub start_in: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Time before a scheduled start ('--start-at', '--start-in') to send a notification, e.g. '1m'."
+    )]
+    pub pre_start: Option<Duration>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["start_at", "start_in"],
+        help = "Start the clock of countdown, timer or pomodoro (current phase) right after launching. A finished clock starts again."
+    )]
+    pub start: bool,
+
     #[arg(
         long,
         short = 'e',
@@ -37,6 +89,20 @@ pub struct Args {
     )]
     pub event: Option<Event>,
 
+    #[arg(
+        long,
+        help = "Name of a preset to start with (see '--add-preset'). It sets countdown or pomodoro durations."
+    )]
+    pub preset: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "NAME=DURATION",
+        value_parser = parse_preset,
+        help = "Add a named preset: 'name=duration' for countdown or 'name=work/pause' for pomodoro. Duration formats: see '--countdown'. Examples: 'tea=3:00', 'deep=50:00/10:00'. Can be used multiple times. An empty value removes a preset."
+    )]
+    pub add_preset: Vec<(String, Option<PresetValue>)>,
+
     #[arg(long, short = 'd', help = "Show deciseconds.")]
     pub decis: bool,
 
@@ -49,20 +115,152 @@ pub struct Args {
     #[arg(long, value_enum, help = "Open menu.")]
     pub menu: bool,
 
+    #[arg(long, help = "Zen mode: Show the clock only (no header, no footer).")]
+    pub zen: bool,
+
+    #[arg(
+        long,
+        help = "Kiosk mode: Zen mode, but read-only. All keys are ignored, except 'ctrl+u' to unlock (or to stop an alarm)."
+    )]
+    pub kiosk: bool,
+
     #[arg(long, short = 'v', value_enum, help = "Enable/disable Vim motions.")]
     pub vim: Option<Toggle>,
 
     #[arg(long, short = 'r', help = "Reset stored values to defaults.")]
     pub reset: bool,
 
+    #[arg(
+        long,
+        env = "TIMR_INSTANCE",
+        value_parser = storage::parse_instance,
+        help = "Name of an instance to run with its own stored data, e.g. to run multiple instances in parallel. Allowed characters: 'a-z', 'A-Z', '0-9', '-', '_'."
+    )]
+    pub instance: Option<String>,
+
+    #[arg(
+        long,
+        env = "TIMR_DATA_DIR",
+        help = "Directory for stored data. If not set, a 'data' directory in 'TIMR_CONFIG_DIR' or standard application data directory is used (check README for details).",
+        value_hint = clap::ValueHint::DirPath,
+    )]
+    pub data_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Portable mode: Keep all files (data, logs) in a 'timr-tui-data' directory next to the executable."
+    )]
+    pub portable: bool,
+
     #[arg(
         long,
         short,
         value_enum,
-        help = "Enable/disable desktop notifications. Experimental."
+        help = "Enable/disable notifications (see '--notification-backend'). Experimental."
     )]
     pub notification: Option<Toggle>,
 
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        help = "Backend(s) to send notifications with. Combine several backends separated by commas, e.g. 'bell,osc9'. Default: 'desktop'."
+    )]
+    pub notification_backend: Option<Vec<NotificationBackend>>,
+
+    #[arg(
+        long,
+        value_name = "KIND=TEMPLATE",
+        value_parser = parse_notification_template,
+        help = "Template of a notification message. Kinds: countdown, timer, event, work, pause. Placeholders: {name}, {duration}, {round}, {time}, {next}. Example: 'pause=Break over - round {round} starts'. Can be used multiple times. An empty template restores the default message."
+    )]
+    pub notification_template: Vec<(NotificationKind, String)>,
+
+    #[arg(long, value_enum, help = "Urgency of desktop notifications.")]
+    pub notification_urgency: Option<NotificationUrgency>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Time to show desktop notifications, e.g. '10'. '0' means notifications never expire."
+    )]
+    pub notification_timeout: Option<Duration>,
+
+    #[arg(
+        long,
+        help = "Icon (name or path) of desktop notifications. An empty value restores the default icon."
+    )]
+    pub notification_icon: Option<String>,
+
+    #[arg(
+        long,
+        help = "Application name of notifications. An empty value restores the default name."
+    )]
+    pub notification_app_name: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Enable/disable alarm mode to repeat sound and notification of a finished clock until any key is pressed."
+    )]
+    pub alarm: Option<Toggle>,
+
+    #[arg(
+        long,
+        value_parser = parse_alarm_interval,
+        help = "Time between repeats of an alarm, e.g. '30'. Default: '10'."
+    )]
+    pub alarm_interval: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Maximum time to repeat an alarm, e.g. '10:00'. '0' means no limit. Default: '5:00'."
+    )]
+    pub alarm_max: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Time to add to a countdown or pomodoro by pressing '+', e.g. '30'. Default: '1:00'."
+    )]
+    pub extend: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Time to add to a countdown or pomodoro by pressing '*', e.g. '10:00'. Default: '5:00'."
+    )]
+    pub extend_more: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Time to restart a finished countdown or pomodoro by pressing 'n', e.g. '2:00'. Default: '5:00'."
+    )]
+    pub snooze: Option<Duration>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Confirm to reset a clock which has been running longer than given time, e.g. '1:00'. '0' means no confirmation. Default: '0'."
+    )]
+    pub confirm_reset: Option<Duration>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "What to do with running clocks on quit: Ask, keep them running while the app is closed or pause them. Default: 'ask'."
+    )]
+    pub quit_running: Option<QuitRunning>,
+
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Interval to save data while running, e.g. '1m'. Data is saved after changes (e.g. starting or editing a clock) and finished clocks, too. '0' disables autosave. Default: '30'."
+    )]
+    pub autosave: Option<Duration>,
+
     #[arg(
         long,
         value_enum,
@@ -70,6 +268,41 @@ pub struct Args {
     )]
     pub blink: Option<Toggle>,
 
+    #[arg(
+        long,
+        value_enum,
+        help = "Enable/disable urgency colors (green, yellow, red) while a countdown or pomodoro clock approaches zero."
+    )]
+    pub urgency: Option<Toggle>,
+
+    #[arg(
+        long,
+        value_parser = parse_urgency_threshold,
+        help = "Threshold to switch urgency color to yellow. Formats: percentage of time done (e.g. '50%') or remaining time (e.g. '5:00'). Default: '50%'."
+    )]
+    pub urgency_warn: Option<UrgencyThreshold>,
+
+    #[arg(
+        long,
+        value_parser = parse_urgency_threshold,
+        help = "Threshold to switch urgency color to red. Formats: percentage of time done (e.g. '80%') or remaining time (e.g. '1:00'). Default: '80%'."
+    )]
+    pub urgency_critical: Option<UrgencyThreshold>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Enable/disable showing current clock in title of terminal window or tab (OSC 0)."
+    )]
+    pub terminal_title: Option<Toggle>,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Enable/disable reporting progress to terminal to show it in taskbar or tab (OSC 9;4). Experimental."
+    )]
+    pub terminal_progress: Option<Toggle>,
+
     #[cfg(feature = "sound")]
     #[arg(
         long,
@@ -80,6 +313,110 @@ pub struct Args {
     )]
     pub sound: Option<PathBuf>,
 
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_enum,
+        help = "Built-in tone to play if no sound file is given or a sound file can't be decoded. 'off' to play no tone. Default: 'beep'. Experimental."
+    )]
+    pub sound_tone: Option<Tone>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u8).range(0..=MAX_VOLUME as i64),
+        help = "Volume of sounds (0-100). Default: 100. Experimental."
+    )]
+    pub volume: Option<u8>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Name of output device to play sounds with. Use 'list' to print names of all available devices. An empty value restores the default device. Experimental."
+    )]
+    pub sound_device: Option<String>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_parser = clock_types_parser(&["countdown", "timer", "work", "pause"]),
+        value_delimiter = ',',
+        num_args = 0..,
+        help = "Clock types to play a soft tick each second while running, separated by commas (e.g. 'work,countdown'). Pass no value to disable it. Experimental."
+    )]
+    pub tick_sound: Option<Vec<NotificationKind>>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_parser = clock_types_parser(&["countdown", "work", "pause"]),
+        value_delimiter = ',',
+        num_args = 0..,
+        help = "Clock types to beep during the last three seconds (3-2-1), separated by commas (e.g. 'countdown,work,pause'). Pass no value to disable it. Experimental."
+    )]
+    pub countdown_beep: Option<Vec<NotificationKind>>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_enum,
+        help = "Enable/disable increasing volume of repeated alarm sounds (see '--alarm'). Experimental."
+    )]
+    pub alarm_volume_ramp: Option<Toggle>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Path to sound file (.mp3 or .wav) to play if a countdown is done. Falls back to '--sound'. Experimental.",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = sound_file_parser,
+    )]
+    pub sound_countdown: Option<PathBuf>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Path to sound file (.mp3 or .wav) to play if a pomodoro work phase is done. Falls back to '--sound'. Experimental.",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = sound_file_parser,
+    )]
+    pub sound_work: Option<PathBuf>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Path to sound file (.mp3 or .wav) to play if a pomodoro pause phase is done. Falls back to '--sound'. Experimental.",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = sound_file_parser,
+    )]
+    pub sound_pause: Option<PathBuf>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Path to sound file (.mp3 or .wav) to play if an event is reached. Falls back to '--sound'. Experimental.",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = sound_file_parser,
+    )]
+    pub sound_event: Option<PathBuf>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        help = "Path to sound file (.mp3 or .wav) to play as pre-alert (see '--pre-alert'). Falls back to '--sound'. Experimental.",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = sound_file_parser,
+    )]
+    pub sound_pre_alert: Option<PathBuf>,
+
+    #[cfg(feature = "sound")]
+    #[arg(
+        long,
+        value_parser = duration::parse_long_duration,
+        help = "Remaining time of a running countdown or pomodoro clock to play a pre-alert sound, e.g. '10'. Experimental."
+    )]
+    pub pre_alert: Option<Duration>,
+
     #[arg(
         long,
         // allows both --log=path and --log path syntax
@@ -94,6 +431,36 @@ pub struct Args {
     pub log: Option<PathBuf>,
 }
 
+#[derive(Subcommand)]
+pub enum Command {
+    /// Export settings and state into a portable JSON bundle (check README for details).
+    Export {
+        #[arg(
+            long,
+            short,
+            help = "File to write the bundle to. If not set, it's printed to stdout.",
+            value_hint = clap::ValueHint::FilePath,
+        )]
+        output: Option<PathBuf>,
+    },
+    /// Import settings and state from a bundle created by `export`.
+    Import {
+        #[arg(help = "File of the bundle.", value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+    },
+}
+
+#[cfg(feature = "sound")]
+/// Parser of clock types limited to given names of `NotificationKind`
+fn clock_types_parser(
+    names: &'static [&'static str],
+) -> impl TypedValueParser<Value = NotificationKind> {
+    clap::builder::PossibleValuesParser::new(names).map(|name| {
+        // safe unwrap: names are validated by `PossibleValuesParser`
+        NotificationKind::from_str(&name, false).unwrap()
+    })
+}
+
 #[cfg(feature = "sound")]
 /// Custom parser for sound file
 fn sound_file_parser(s: &str) -> Result<PathBuf, SoundError> {
@@ -101,3 +468,24 @@ fn sound_file_parser(s: &str) -> Result<PathBuf, SoundError> {
     sound::validate_sound_file(&path)?;
     Ok(path)
 }
+
+#[cfg(all(test, feature = "sound"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_clock_types() {
+        let args = Args::try_parse_from(["timr-tui", "--tick-sound", "timer,work"]).unwrap();
+        assert_eq!(
+            args.tick_sound,
+            Some(vec![NotificationKind::Timer, NotificationKind::Work])
+        );
+        let args = Args::try_parse_from(["timr-tui", "--countdown-beep"]).unwrap();
+        assert_eq!(args.countdown_beep, Some(vec![]));
+        // unsupported types
+        assert!(Args::try_parse_from(["timr-tui", "--tick-sound", "event"]).is_err());
+        assert!(Args::try_parse_from(["timr-tui", "--countdown-beep", "timer"]).is_err());
+        assert!(Args::try_parse_from(["timr-tui", "--countdown-beep", "event"]).is_err());
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    duration::{self, DurationEx},
+    event,
+    preset::{self, Preset},
+    storage::{self, AppStorage},
+};
+use color_eyre::eyre::{Result, WrapErr, eyre};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use time::macros::format_description;
+
+/// Current version of `Bundle` format
+pub const VERSION: u32 = 1;
+
+/// Portable bundle of stored data to export and import it,
+/// e.g. to move a setup to another machine or to share it.
+///
+/// Values users might want to edit are stored in a readable format (see `READABLE_KEYS`).
+/// They are validated by the same parsers used by CLI args.
+/// Exact initial values of clocks are kept in `storage`, since the readable format has no fractions of a second.
+/// State of a running session (see `SESSION_KEYS`) is not bundled.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Version of bundle format
+    pub version: u32,
+    /// Initial value of countdown. Formats: see `duration::parse_long_duration`
+    pub countdown: String,
+    /// Initial value of pomodoro work. Formats: see `duration::parse_long_duration`
+    pub work: String,
+    /// Initial value of pomodoro pause. Formats: see `duration::parse_long_duration`
+    pub pause: String,
+    pub event: BundleEvent,
+    /// Named presets. Format: see `preset::parse_preset`, e.g. `tea=3:00` or `deep=50:00/10:00`.
+    #[serde(default)]
+    pub presets: Vec<String>,
+    /// All other stored data incl. its `version` (see `AppStorage`).
+    /// Data of a previous version is migrated by importing it.
+    pub storage: Value,
+}
+
+/// Keys of `AppStorage` bundled by readable fields of `Bundle`.
+/// Note: `initial_value_*` are kept to import values which are not edited exactly (see `import_duration`).
+const READABLE_KEYS: [&str; 5] = [
+    "current_value_countdown",
+    "elapsed_value_countdown",
+    "current_value_work",
+    "current_value_pause",
+    "event",
+];
+
+/// Keys of `AppStorage` holding state of a running session
+const SESSION_KEYS: [&str; 2] = ["running_clocks", "scheduled_start"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleEvent {
+    /// Format: `YYYY-MM-DD HH:MM:SS` (see `event::parse_event`)
+    pub time: String,
+    pub title: Option<String>,
+}
+
+impl Bundle {
+    pub fn export(data: &AppStorage) -> Result<Self> {
+        let time = data.event.date_time.format(format_description!(
+            "[year]-[month]-[day] [hour]:[minute]:[second]"
+        ))?;
+        Ok(Self {
+            version: VERSION,
+            countdown: DurationEx::from(data.initial_value_countdown).to_string(),
+            work: DurationEx::from(data.initial_value_work).to_string(),
+            pause: DurationEx::from(data.initial_value_pause).to_string(),
+            event: BundleEvent {
+                time,
+                title: data.event.title.clone(),
+            },
+            presets: data
+                .presets
+                .presets
+                .iter()
+                .map(|p| format!("{}={}", p.name, p.value))
+                .collect(),
+            storage: export_storage(data)?,
+        })
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path)?;
+        let bundle = serde_json::from_reader(file)?;
+        Ok(bundle)
+    }
+
+    pub fn import(self) -> Result<AppStorage> {
+        if self.version > VERSION {
+            return Err(eyre!(
+                "Unsupported bundle version {} (supported up to {VERSION})",
+                self.version
+            ));
+        }
+        let mut data =
+            storage::from_value(import_storage(self.storage)).wrap_err("Invalid storage")?;
+
+        let countdown =
+            import_duration("countdown", &self.countdown, data.initial_value_countdown)?;
+        data.initial_value_countdown = countdown;
+        data.current_value_countdown = countdown;
+        data.elapsed_value_countdown = Duration::ZERO;
+        let work = import_duration("work", &self.work, data.initial_value_work)?;
+        data.initial_value_work = work;
+        data.current_value_work = work;
+        let pause = import_duration("pause", &self.pause, data.initial_value_pause)?;
+        data.initial_value_pause = pause;
+        data.current_value_pause = pause;
+
+        let mut event =
+            event::parse_event(&self.event.time).map_err(|err| eyre!("Invalid event: {err}"))?;
+        event.title = self.event.title.filter(|title| !title.trim().is_empty());
+        data.event = event;
+
+        data.presets.presets = self
+            .presets
+            .iter()
+            .map(|p| match preset::parse_preset(p)? {
+                (name, Some(value)) => Ok(Preset { name, value }),
+                (name, None) => Err(eyre!("Missing value of preset '{name}'")),
+            })
+            .collect::<Result<_>>()?;
+
+        // e.g. bundled by a previous version
+        data.running_clocks = None;
+        data.scheduled_start = None;
+
+        Ok(data)
+    }
+}
+
+/// Removes values of readable fields and session state from stored data
+fn export_storage(data: &AppStorage) -> Result<Value> {
+    let mut value = serde_json::to_value(data)?;
+    if let Value::Object(map) = &mut value {
+        for key in READABLE_KEYS.iter().chain(SESSION_KEYS.iter()) {
+            map.remove(*key);
+        }
+        // named presets are bundled by `Bundle::presets`, recently used ones are kept
+        if let Some(Value::Object(presets)) = map.get_mut("presets") {
+            presets.insert("presets".into(), Value::Array(Vec::new()));
+        }
+    }
+    Ok(value)
+}
+
+/// Adds default values of all keys removed by `export_storage`
+/// to convert stored data into `AppStorage`. They are replaced by `Bundle::import`.
+fn import_storage(mut value: Value) -> Value {
+    if let (Value::Object(map), Ok(Value::Object(defaults))) =
+        (&mut value, serde_json::to_value(AppStorage::default()))
+    {
+        for (key, default) in defaults {
+            if READABLE_KEYS.contains(&key.as_str()) || SESSION_KEYS.contains(&key.as_str()) {
+                map.entry(key).or_insert(default);
+            }
+        }
+    }
+    value
+}
+
+/// Parses a readable duration. If it's not edited, the `stored` one is used to keep its fractions of a second.
+fn import_duration(name: &str, value: &str, stored: Duration) -> Result<Duration> {
+    let parsed = duration::parse_long_duration(value)
+        .wrap_err_with(|| format!("Invalid {name} '{value}'"))?;
+    let exported = duration::parse_long_duration(&DurationEx::from(stored).to_string()).ok();
+    Ok(if exported == Some(parsed) {
+        stored
+    } else {
+        parsed
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::Content;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_export_import() {
+        let data = AppStorage {
+            content: Content::Pomodoro,
+            initial_value_countdown: Duration::from_secs(2 * 86400 + 3600),
+            initial_value_work: Duration::from_secs(50 * 60),
+            current_value_work: Duration::from_secs(10 * 60),
+            ..AppStorage::default()
+        };
+        let bundle = Bundle::export(&data).unwrap();
+        assert_eq!(bundle.countdown, "2d 01:00:00");
+        assert_eq!(bundle.work, "50:00");
+
+        let json = serde_json::to_string(&bundle).unwrap();
+        let imported = serde_json::from_str::<Bundle>(&json)
+            .unwrap()
+            .import()
+            .unwrap();
+        assert_eq!(imported.content, Content::Pomodoro);
+        assert_eq!(
+            imported.initial_value_countdown,
+            data.initial_value_countdown
+        );
+        // clocks start from their initial values
+        assert_eq!(imported.current_value_work, Duration::from_secs(50 * 60));
+        assert_eq!(imported.event.date_time, data.event.date_time);
+        assert_eq!(imported.event.title, data.event.title);
+    }
+
+    #[test]
+    fn test_export_import_millis() {
+        let data = AppStorage {
+            initial_value_countdown: Duration::from_millis(90_500),
+            initial_value_work: Duration::from_millis(1_500),
+            ..AppStorage::default()
+        };
+        let mut bundle = Bundle::export(&data).unwrap();
+        assert_eq!(bundle.countdown, "1:30");
+        // edited value replaces stored one
+        bundle.work = "2:00".into();
+        let imported = bundle.import().unwrap();
+        assert_eq!(
+            imported.initial_value_countdown,
+            Duration::from_millis(90_500)
+        );
+        assert_eq!(
+            imported.current_value_countdown,
+            Duration::from_millis(90_500)
+        );
+        assert_eq!(imported.initial_value_work, Duration::from_secs(120));
+        assert_eq!(imported.initial_value_pause, data.initial_value_pause);
+    }
+
+    #[test]
+    fn test_export_storage() {
+        let data = AppStorage {
+            running_clocks: Some(storage::RunningClocks {
+                since: std::time::SystemTime::now(),
+                countdown: true,
+                timer: false,
+                pomodoro: false,
+            }),
+            ..AppStorage::default()
+        };
+        let bundle = Bundle::export(&data).unwrap();
+        // values are bundled once only, except exact initial values
+        for key in ["current_value_work", "event"] {
+            assert!(bundle.storage.get(key).is_none());
+        }
+        assert!(bundle.storage.get("initial_value_work").is_some());
+        assert_eq!(bundle.storage["presets"]["presets"], serde_json::json!([]));
+        // no session state
+        assert!(bundle.storage.get("running_clocks").is_none());
+        assert!(bundle.storage.get("scheduled_start").is_none());
+        assert!(bundle.import().unwrap().running_clocks.is_none());
+    }
+
+    #[test]
+    fn test_import_edited() {
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        bundle.work = "1:30:00".into();
+        bundle.presets = vec!["tea=3:00".into(), "deep=50:00/10:00".into()];
+        bundle.event = BundleEvent {
+            time: "2025-12-24 18:00:00".into(),
+            title: Some("Christmas".into()),
+        };
+        let data = bundle.import().unwrap();
+        assert_eq!(data.initial_value_work, Duration::from_secs(90 * 60));
+        assert_eq!(data.current_value_work, Duration::from_secs(90 * 60));
+        assert_eq!(data.event.date_time, datetime!(2025-12-24 18:00:00));
+        assert_eq!(data.event.title.as_deref(), Some("Christmas"));
+        assert_eq!(data.presets.presets.len(), 2);
+        assert_eq!(
+            data.presets.get("deep").map(|p| p.value.to_string()),
+            Some("50:00/10:00".into())
+        );
+    }
+
+    #[test]
+    fn test_import_invalid() {
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        bundle.pause = "5:99".into();
+        assert!(bundle.import().is_err());
+
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        bundle.event.time = "2025-13-01 00:00:00".into();
+        assert!(bundle.import().is_err());
+
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        bundle.presets = vec!["tea=".into()];
+        assert!(bundle.import().is_err());
+
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        bundle.version = VERSION + 1;
+        assert!(bundle.import().is_err());
+
+        let mut bundle = Bundle::export(&AppStorage::default()).unwrap();
+        // missing fields of current version
+        bundle.storage = serde_json::json!({ "version": storage::VERSION, "content": "Timer" });
+        assert!(bundle.import().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use clap::ValueEnum;
 use ratatui::symbols::shade;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 use strum::EnumString;
 use time::{OffsetDateTime, format_description};
 
@@ -50,7 +51,7 @@ pub enum ClockTypeId {
     Event,
 }
 
-#[derive(Debug, Copy, Clone, ValueEnum, Default, Serialize, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, ValueEnum, Default, Serialize, Deserialize)]
 pub enum Style {
     #[default]
     #[value(name = "full", alias = "f")]
@@ -212,6 +213,27 @@ pub enum AppEditMode {
     Event,
 }
 
+/// Built-in tones generated in code (see `sound`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum Tone {
+    /// Single sine beep
+    #[default]
+    #[value(name = "beep")]
+    Beep,
+    /// Two decaying notes
+    #[value(name = "chime")]
+    Chime,
+    /// Three short beeps
+    #[value(name = "triple-beep")]
+    TripleBeep,
+    /// Rising sweeps
+    #[value(name = "rising-alarm")]
+    RisingAlarm,
+    /// No tone at all
+    #[value(name = "off")]
+    Off,
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Toggle {
     #[value(name = "on")]
@@ -239,6 +261,40 @@ impl From<Toggle> for bool {
     }
 }
 
+/// What to do with running clocks on quit
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum QuitRunning {
+    /// Ask to keep clocks running or to pause them
+    #[default]
+    #[value(name = "ask")]
+    Ask,
+    /// Keep clocks running while the app is closed
+    #[value(name = "keep")]
+    Keep,
+    /// Pause clocks
+    #[value(name = "pause")]
+    Pause,
+}
+
+/// Durations used by the extend (`+`, `*`) and snooze (`n`) keys of countdowns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtendSettings {
+    pub extend: Duration,
+    pub extend_more: Duration,
+    pub snooze: Duration,
+}
+
+impl Default for ExtendSettings {
+    fn default() -> Self {
+        Self {
+            extend: Duration::from_secs(60),
+            extend_more: Duration::from_secs(5 * 60),
+            snooze: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
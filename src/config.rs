@@ -1,28 +1,77 @@
 use crate::constants::APP_NAME;
 use color_eyre::eyre::{Result, eyre};
 use directories::ProjectDirs;
+use std::ffi::OsString;
 use std::fs;
 use std::path::PathBuf;
 
+/// Environment variable to override the directory of all files (logs, data)
+pub const CONFIG_DIR_ENV: &str = "TIMR_CONFIG_DIR";
+/// Alias of `CONFIG_DIR_ENV` (lower precedence)
+pub const HOME_DIR_ENV: &str = "TIMR_HOME";
+
 pub struct Config {
     pub log_dir: PathBuf,
     pub data_dir: PathBuf,
 }
 
 impl Config {
-    pub fn init() -> Result<Self> {
+    /// `data_dir` overrides the directory of stored data.
+    /// `portable` keeps all files next to the executable.
+    pub fn init(data_dir: Option<PathBuf>, portable: bool) -> Result<Self> {
+        let config_dir = get_config_dir_env(|key| std::env::var_os(key));
+        Self::init_with(data_dir, portable, config_dir)
+    }
+
+    /// Same as `init`, but with a given value of `CONFIG_DIR_ENV`
+    fn init_with(
+        data_dir: Option<PathBuf>,
+        portable: bool,
+        config_dir: Option<OsString>,
+    ) -> Result<Self> {
+        let base_dir = get_base_dir(portable, config_dir)?;
+
         // default logs dir
-        let log_dir = get_default_state_dir()?.join("logs");
+        let log_dir = base_dir.join("logs");
         fs::create_dir_all(&log_dir)?;
 
         // default data dir
-        let data_dir = get_default_state_dir()?.join("data");
+        let data_dir = data_dir.unwrap_or_else(|| base_dir.join("data"));
         fs::create_dir_all(&data_dir)?;
 
         Ok(Self { log_dir, data_dir })
     }
 }
 
+/// Directory set by `CONFIG_DIR_ENV` or its alias `HOME_DIR_ENV`. Empty values are ignored.
+fn get_config_dir_env(var: impl Fn(&str) -> Option<OsString>) -> Option<OsString> {
+    [CONFIG_DIR_ENV, HOME_DIR_ENV]
+        .into_iter()
+        .filter_map(var)
+        .find(|dir| !dir.is_empty())
+}
+
+/// Base directory of all files. In order of precedence:
+/// (1) portable mode: `{APP_NAME}-data` next to the executable
+/// (2) `config_dir` (see `CONFIG_DIR_ENV`)
+/// (3) standard application state directory
+///
+/// Note: A data directory given by `--data-dir` (or `TIMR_DATA_DIR`) overrides all of them for stored data.
+fn get_base_dir(portable: bool, config_dir: Option<OsString>) -> Result<PathBuf> {
+    if portable {
+        let exe = std::env::current_exe()?;
+        let dir = exe
+            .parent()
+            .ok_or_else(|| eyre!("Failed to get directory of executable {:?}", exe))?;
+        return Ok(dir.join(format!("{APP_NAME}-data")));
+    }
+
+    match config_dir {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => get_default_state_dir(),
+    }
+}
+
 pub fn get_project_dir() -> Result<ProjectDirs> {
     let dirs = ProjectDirs::from("", "", APP_NAME)
         .ok_or_else(|| eyre!("Failed to get project directories"))?;
@@ -39,3 +88,84 @@ fn get_default_state_dir() -> Result<PathBuf> {
 
     Ok(directory)
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_init_config_dir() {
+        let config = TempDir::new().unwrap();
+        let cfg = Config::init_with(None, false, Some(config.path().into())).unwrap();
+        assert_eq!(cfg.log_dir, config.path().join("logs"));
+        assert_eq!(cfg.data_dir, config.path().join("data"));
+        assert!(cfg.log_dir.is_dir());
+        assert!(cfg.data_dir.is_dir());
+    }
+
+    #[test]
+    fn test_init_data_dir() {
+        let config = TempDir::new().unwrap();
+        let data = TempDir::new().unwrap();
+        let cfg = Config::init_with(
+            Some(data.path().join("custom")),
+            false,
+            Some(config.path().into()),
+        )
+        .unwrap();
+        // `data_dir` has precedence over config dir for data only
+        assert_eq!(cfg.data_dir, data.path().join("custom"));
+        assert!(cfg.data_dir.is_dir());
+        assert_eq!(cfg.log_dir, config.path().join("logs"));
+    }
+
+    #[test]
+    fn test_base_dir() {
+        let exe_dir = std::env::current_exe()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        // portable mode has precedence over config dir
+        assert_eq!(
+            get_base_dir(true, Some("/tmp/timr".into())).unwrap(),
+            exe_dir.join("timr-tui-data")
+        );
+        assert_eq!(
+            get_base_dir(false, Some("/tmp/timr".into())).unwrap(),
+            PathBuf::from("/tmp/timr")
+        );
+        // empty value is ignored
+        assert_eq!(
+            get_base_dir(false, Some("".into())).unwrap(),
+            get_base_dir(false, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_config_dir_env() {
+        let env = |vars: &'static [(&str, &str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| OsString::from(v))
+            }
+        };
+        assert_eq!(
+            get_config_dir_env(env(&[("TIMR_CONFIG_DIR", "/a"), ("TIMR_HOME", "/b")])),
+            Some("/a".into())
+        );
+        // alias
+        assert_eq!(
+            get_config_dir_env(env(&[("TIMR_HOME", "/b")])),
+            Some("/b".into())
+        );
+        assert_eq!(
+            get_config_dir_env(env(&[("TIMR_CONFIG_DIR", ""), ("TIMR_HOME", "/b")])),
+            Some("/b".into())
+        );
+        assert_eq!(get_config_dir_env(env(&[])), None);
+    }
+}
@@ -22,6 +22,7 @@ pub const ONE_SECOND: Duration = Duration::from_secs(1);
 pub const ONE_MINUTE: Duration = Duration::from_secs(SECS_PER_MINUTE);
 pub const ONE_HOUR: Duration = Duration::from_secs(MINS_PER_HOUR * SECS_PER_MINUTE);
 pub const ONE_DAY: Duration = Duration::from_secs(HOURS_PER_DAY * MINS_PER_HOUR * SECS_PER_MINUTE);
+pub const ONE_WEEK: Duration = ONE_DAY.saturating_mul(7);
 pub const ONE_YEAR: Duration =
     Duration::from_secs(DAYS_PER_YEAR * HOURS_PER_DAY * MINS_PER_HOUR * SECS_PER_MINUTE);
 
@@ -373,6 +374,80 @@ impl fmt::Display for DurationEx {
     }
 }
 
+/// Units of values with suffixes, e.g. `2w`, `1.5h`
+const UNITS: [(char, Duration); 6] = [
+    ('y', ONE_YEAR),
+    ('w', ONE_WEEK),
+    ('d', ONE_DAY),
+    ('h', ONE_HOUR),
+    ('m', ONE_MINUTE),
+    ('s', ONE_SECOND),
+];
+
+const UNITS_HINT: &str = "Units: 'y', 'w', 'd', 'h', 'm', 's'";
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Multiplies `unit` by a decimal `value`, e.g. `1.5`
+fn mul_decimal(unit: Duration, value: &str) -> Result<Duration, Report> {
+    let (int, fraction) = value.split_once('.').unwrap_or((value, ""));
+    ensure!(
+        !int.is_empty() || !fraction.is_empty(),
+        "Invalid value: '{}'",
+        value
+    );
+    ensure!(
+        fraction.len() <= 9,
+        "Too many decimal places: '{}' (max. 9)",
+        value
+    );
+    let parse = |digits: &str| -> Result<u128, Report> {
+        if digits.is_empty() {
+            return Ok(0);
+        }
+        digits
+            .parse::<u128>()
+            .map_err(|_| eyre!("Invalid value: '{}'", value))
+    };
+    // value in nanos of its unit, e.g. `1.5` -> `1_500_000_000`
+    let scaled = parse(int)?
+        .saturating_mul(NANOS_PER_SECOND)
+        .saturating_add(parse(fraction)? * 10u128.pow(9 - fraction.len() as u32));
+    let nanos = unit.as_nanos().saturating_mul(scaled) / NANOS_PER_SECOND;
+    let secs = u64::try_from(nanos / NANOS_PER_SECOND).unwrap_or(u64::MAX);
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SECOND) as u32))
+}
+
+/// Parses a part of values with unit suffixes, e.g. `45s`, `1h30m` or `1.5h`.
+/// `units_used` collects all units to avoid using a unit twice.
+fn parse_unit_part(part: &str, units_used: &mut Vec<char>) -> Result<Duration, Report> {
+    let mut total = Duration::ZERO;
+    let mut rest = part;
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .ok_or_else(|| eyre!("Missing unit after '{}'. {}", rest, UNITS_HINT))?;
+        let (value, tail) = rest.split_at(end);
+        // safe unwrap: `end` is a position in `rest`
+        let unit = tail.chars().next().unwrap();
+        ensure!(!value.is_empty(), "Missing value before '{}'", unit);
+        let (_, unit_duration) = UNITS
+            .iter()
+            .find(|(u, _)| *u == unit)
+            .ok_or_else(|| eyre!("Invalid unit: '{}'. {}", unit, UNITS_HINT))?;
+        ensure!(!units_used.contains(&unit), "Unit '{}' is used twice", unit);
+        units_used.push(unit);
+        total = total.saturating_add(mul_decimal(*unit_duration, value)?);
+        rest = &tail[unit.len_utf8()..];
+    }
+    Ok(total)
+}
+
+/// Checks for values with unit suffixes, e.g. `1h30m`
+fn has_units(s: &str) -> bool {
+    s.contains(|c: char| c.is_alphabetic())
+}
+
 /// Parse seconds (must be < 60)
 fn parse_seconds(s: &str) -> Result<u8, Report> {
     let secs = s.parse::<u8>().map_err(|_| eyre!("Invalid seconds"))?;
@@ -387,14 +462,24 @@ fn parse_minutes(m: &str) -> Result<u8, Report> {
     Ok(mins)
 }
 
-/// Parse hours
-fn parse_hours(h: &str) -> Result<u8, Report> {
-    let hours = h.parse::<u8>().map_err(|_| eyre!("Invalid hours"))?;
+/// Parse hours (no limit, e.g. `100:00:00`)
+fn parse_hours(h: &str) -> Result<u64, Report> {
+    let hours = h.parse::<u64>().map_err(|_| eyre!("Invalid hours"))?;
     Ok(hours)
 }
 
-/// Parses  `Duration` from `hh:mm:ss`, `mm:ss` or `ss`
+/// Parses  `Duration` from `hh:mm:ss`, `mm:ss` or `ss` (any hours, limited by `MAX_DURATION`)
+/// or from values with unit suffixes, e.g. `90m`, `1h30m`, `1.5h`, `2h 15s` (see `UNITS`)
 pub fn parse_duration(arg: &str) -> Result<Duration, Report> {
+    if has_units(arg) {
+        let mut units_used = vec![];
+        let mut total = Duration::ZERO;
+        for part in arg.split_whitespace() {
+            total = total.saturating_add(parse_unit_part(part, &mut units_used)?);
+        }
+        return Ok(min(MAX_DURATION, total));
+    }
+
     let parts: Vec<&str> = arg.split(':').collect();
 
     let (hours, minutes, seconds) = match parts.as_slice() {
@@ -414,7 +499,7 @@ pub fn parse_duration(arg: &str) -> Result<Duration, Report> {
             let h = parse_hours(hh)?;
             let m = parse_minutes(mm)?;
             let s = parse_seconds(ss)?;
-            (h as u64, m as u64, s as u64)
+            (h, m as u64, s as u64)
         }
         _ => {
             return Err(eyre!(
@@ -423,41 +508,36 @@ pub fn parse_duration(arg: &str) -> Result<Duration, Report> {
         }
     };
 
-    let total_seconds = hours * 3600 + minutes * 60 + seconds;
-    Ok(Duration::from_secs(total_seconds))
+    let total = ONE_HOUR
+        .saturating_mul(u32::try_from(hours).unwrap_or(u32::MAX))
+        .saturating_add(Duration::from_secs(minutes * 60 + seconds));
+    Ok(min(MAX_DURATION, total))
 }
 
-/// Similar to `parse_duration`, but it parses `years` and `days` in addition
-/// Formats: `Yy Dd`, `Yy` or `Dd` in any combination to other time formats
-/// Examples: `10y 3d 12:10:03`, `2d 10:00`, `101y 33`, `5:30`
+/// Parser of all durations (CLI args, presets, bundles etc.) up to `MAX_DURATION`.
+/// Similar to `parse_duration`, but values with unit suffixes can be combined with a time format
+/// Formats: `Yy Dd`, `Yy` or `Dd` (or any other unit, see `UNITS`) in any combination to other time formats
+/// Examples: `10y 3d 12:10:03`, `2d 10:00`, `101y 33`, `5:30`, `1w 2d`, `1h30m`, `1.5h`
 pub fn parse_long_duration(arg: &str) -> Result<Duration, Report> {
     let arg = arg.trim();
 
-    // parts are separated by whitespaces:
-    // 3 parts: years, days, time
-    let parts: Vec<&str> = arg.split_whitespace().collect();
-    ensure!(parts.len() <= 3, "Invalid format. Too many parts.");
-
     let mut total_duration = Duration::ZERO;
     let mut time_part: Option<&str> = None;
+    let mut units_used = vec![];
 
-    for part in parts {
-        // years
-        if let Some(years_str) = part.strip_suffix('y') {
-            let years = years_str
-                .parse::<u64>()
-                .map_err(|_| eyre!("Invalid years value: '{}'", years_str))?;
-            total_duration = total_duration.saturating_add(ONE_YEAR.saturating_mul(years as u32));
-        }
-        // days
-        else if let Some(days_str) = part.strip_suffix('d') {
-            let days = days_str
-                .parse::<u64>()
-                .map_err(|_| eyre!("Invalid days value: '{}'", days_str))?;
-            total_duration = total_duration.saturating_add(ONE_DAY.saturating_mul(days as u32));
+    // parts are separated by whitespaces
+    for part in arg.split_whitespace() {
+        // values with units, e.g. `1y`, `2d` or `1h30m`
+        if has_units(part) {
+            total_duration = total_duration.saturating_add(parse_unit_part(part, &mut units_used)?);
         }
         // possible time format
         else {
+            ensure!(
+                time_part.is_none(),
+                "Invalid format. Too many time parts: '{}'",
+                part
+            );
             time_part = Some(part);
         }
     }
@@ -598,6 +678,45 @@ mod tests {
         assert!(parse_duration("60:00").is_err()); // invalid minutes
         assert!(parse_duration("abc").is_err()); // invalid input
         assert!(parse_duration("01:02:03:04").is_err()); // too many parts
+        // hours beyond a day
+        assert_eq!(
+            parse_duration("100:00:00").unwrap(),
+            Duration::from_secs(100 * HOUR_IN_SECONDS)
+        );
+        assert_eq!(parse_duration("99999999999:00:00").unwrap(), MAX_DURATION);
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("90m").unwrap(), 90 * ONE_MINUTE);
+        assert_eq!(parse_duration("1h30m").unwrap(), 90 * ONE_MINUTE);
+        assert_eq!(parse_duration("1.5h").unwrap(), 90 * ONE_MINUTE);
+        assert_eq!(parse_duration(".5m").unwrap(), Duration::from_secs(30));
+        assert_eq!(
+            parse_duration("2h 15s").unwrap(),
+            2 * ONE_HOUR + 15 * ONE_SECOND
+        );
+        assert_eq!(parse_duration("2w").unwrap(), 14 * ONE_DAY);
+        assert_eq!(
+            parse_duration("1.25s").unwrap(),
+            Duration::from_millis(1250)
+        );
+        // MAX_DURATION clamping
+        assert_eq!(parse_duration("20000y").unwrap(), MAX_DURATION);
+        assert_eq!(
+            parse_duration("99999999999999999999y").unwrap(),
+            MAX_DURATION
+        );
+        // errors
+        assert!(parse_duration("10x").is_err()); // invalid unit
+        assert!(parse_duration("1h30").is_err()); // missing unit
+        assert!(parse_duration("h").is_err()); // missing value
+        assert!(parse_duration("1h 2h").is_err()); // same unit twice
+        assert!(parse_duration("1h2h").is_err()); // same unit twice
+        assert!(parse_duration("1.2.3h").is_err()); // invalid value
+        assert!(parse_duration(".h").is_err()); // invalid value
+        assert!(parse_duration("1h 30:00").is_err()); // mixed formats
     }
 
     #[test]
@@ -695,8 +814,38 @@ mod tests {
         assert!(parse_long_duration("abc").is_err()); // invalid input
         assert!(parse_long_duration("10y 60:00").is_err()); // invalid minutes in time part
         assert!(parse_long_duration("5d 1:60").is_err()); // invalid seconds in time part
-        assert!(parse_long_duration("1y 2d 3d 4:00").is_err()); // too many parts (4 parts)
-        assert!(parse_long_duration("1y 2d 3h 4m 5s").is_err()); // too many parts (5 parts)
+        assert!(parse_long_duration("1y 2d 3d 4:00").is_err()); // same unit twice
+        assert!(parse_long_duration("1y 2:00 4:00").is_err()); // too many time parts
+    }
+
+    #[test]
+    fn test_parse_long_duration_units() {
+        assert_eq!(
+            parse_long_duration("1y 2d 3h 4m 5s").unwrap(),
+            Duration::from_secs(
+                YEAR_IN_SECONDS
+                    + 2 * DAY_IN_SECONDS
+                    + 3 * HOUR_IN_SECONDS
+                    + 4 * MINUTE_IN_SECONDS
+                    + 5
+            )
+        );
+        assert_eq!(
+            parse_long_duration("1w 2d").unwrap(),
+            Duration::from_secs(9 * DAY_IN_SECONDS)
+        );
+        assert_eq!(
+            parse_long_duration("1.5h").unwrap(),
+            Duration::from_secs(90 * MINUTE_IN_SECONDS)
+        );
+        // units and time format
+        assert_eq!(
+            parse_long_duration("2w 1:00:00").unwrap(),
+            Duration::from_secs(14 * DAY_IN_SECONDS + HOUR_IN_SECONDS)
+        );
+        // errors
+        assert!(parse_long_duration("1w 1d 1w").is_err()); // same unit twice
+        assert!(parse_long_duration("1.5").is_err()); // decimals need a unit
     }
 
     #[test]
@@ -849,4 +998,83 @@ mod tests {
         );
         assert_eq!(cal_dur.millis(), 750, "Should be 750 milliseconds");
     }
+
+    /// Durations to check properties of: edge cases of `Display` and pseudo-random values up to `MAX_DURATION`
+    fn sample_durations() -> Vec<Duration> {
+        let max_secs = MAX_DURATION.as_secs();
+        let mut secs: Vec<u64> = vec![
+            0,
+            9,
+            10,
+            59,
+            60,
+            599,
+            600,
+            HOUR_IN_SECONDS - 1,
+            HOUR_IN_SECONDS,
+            10 * HOUR_IN_SECONDS - 1,
+            10 * HOUR_IN_SECONDS,
+            DAY_IN_SECONDS - 1,
+            DAY_IN_SECONDS,
+            YEAR_IN_SECONDS - 1,
+            YEAR_IN_SECONDS,
+            max_secs,
+        ];
+        // linear congruential generator for reproducible values
+        let mut seed: u64 = 42;
+        for _ in 0..10_000 {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            // spread values over all magnitudes
+            let magnitude = 10u64.pow((seed >> 60) as u32 % 12);
+            secs.push((seed >> 20) % magnitude.min(max_secs + 1));
+        }
+        secs.into_iter().map(Duration::from_secs).collect()
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for d in sample_durations() {
+            let s = DurationEx::from(d).to_string();
+            assert_eq!(
+                parse_long_duration(&s).unwrap(),
+                d,
+                "round trip of '{s}' failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unit_format_round_trip() {
+        for d in sample_durations() {
+            let secs = d.as_secs();
+            let s = format!(
+                "{}y {}d {}h {}m {}s",
+                secs / YEAR_IN_SECONDS,
+                secs % YEAR_IN_SECONDS / DAY_IN_SECONDS,
+                secs % DAY_IN_SECONDS / HOUR_IN_SECONDS,
+                secs % HOUR_IN_SECONDS / MINUTE_IN_SECONDS,
+                secs % MINUTE_IN_SECONDS,
+            );
+            assert_eq!(
+                parse_long_duration(&s).unwrap(),
+                d,
+                "round trip of '{s}' failed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_long_duration_max() {
+        // never exceeds `MAX_DURATION`
+        for s in [
+            "9999y 364d 23:59:59",
+            "10000y",
+            "1000000:00:00",
+            "99999999w",
+        ] {
+            assert!(parse_long_duration(s).unwrap() <= MAX_DURATION, "{s}");
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use time::macros::{datetime, format_description};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Event {
     pub date_time: time::PrimitiveDateTime,
     pub title: Option<String>,
@@ -13,6 +13,7 @@ use crate::constants::TICK_VALUE_MS;
 enum StreamKey {
     Ticks,
     Crossterm,
+    Signals,
 }
 
 #[derive(Clone, Debug)]
@@ -20,6 +21,8 @@ pub enum TuiEvent {
     Error,
     Tick,
     Crossterm(CrosstermEvent),
+    /// Signal to terminate the app (e.g. `SIGTERM`, `SIGHUP`)
+    Terminate,
 }
 
 #[derive(Clone, Debug)]
@@ -42,6 +45,7 @@ impl Default for Events {
             streams: StreamMap::from_iter([
                 (StreamKey::Ticks, tick_stream()),
                 (StreamKey::Crossterm, crossterm_stream()),
+                (StreamKey::Signals, signal_stream()),
             ]),
             app_channel: mpsc::unbounded_channel(),
         }
@@ -94,6 +98,35 @@ fn crossterm_stream() -> Pin<Box<dyn Stream<Item = TuiEvent>>> {
     )
 }
 
+#[cfg(unix)]
+fn signal_stream() -> Pin<Box<dyn Stream<Item = TuiEvent>>> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let signals = [
+        SignalKind::terminate(),
+        SignalKind::hangup(),
+        SignalKind::interrupt(),
+    ]
+    .into_iter()
+    // ignore signals which can't be registered
+    .filter_map(|kind| signal(kind).ok())
+    .map(|signal| {
+        futures::stream::unfold(signal, |mut signal| async move {
+            signal.recv().await.map(|_| (TuiEvent::Terminate, signal))
+        })
+        .boxed()
+    });
+    Box::pin(futures::stream::select_all(signals))
+}
+
+#[cfg(not(unix))]
+fn signal_stream() -> Pin<Box<dyn Stream<Item = TuiEvent>>> {
+    Box::pin(
+        futures::stream::once(tokio::signal::ctrl_c())
+            .filter_map(|result| async move { result.ok().map(|_| TuiEvent::Terminate) }),
+    )
+}
+
 pub trait TuiEventHandler {
     fn update(&mut self, _: TuiEvent) -> Option<TuiEvent>;
 }
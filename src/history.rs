@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+
+/// Max. number of snapshots to undo
+pub const HISTORY_LIMIT: usize = 20;
+
+/// Bounded undo/redo stack of snapshots
+#[derive(Debug)]
+pub struct History<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+        }
+    }
+}
+
+impl<T> History<T> {
+    /// Stores a snapshot taken before a change.
+    /// Any change drops all snapshots to redo.
+    pub fn push(&mut self, snapshot: T) {
+        self.redo.clear();
+        self.push_undo(snapshot);
+    }
+
+    fn push_undo(&mut self, snapshot: T) {
+        if self.undo.len() >= HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(snapshot);
+    }
+
+    /// Returns previous snapshot to restore.
+    /// `current` snapshot is stored to redo it later.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let prev = self.undo.pop_back()?;
+        self.redo.push(current);
+        Some(prev)
+    }
+
+    /// Returns next snapshot to restore.
+    /// `current` snapshot is stored to undo it again.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.push_undo(current);
+        Some(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_undo_redo() {
+        let mut h = History::default();
+        // nothing to undo or redo
+        assert_eq!(h.undo(0), None);
+        assert_eq!(h.redo(0), None);
+
+        h.push(1);
+        h.push(2);
+        // current: 3
+        assert_eq!(h.undo(3), Some(2));
+        assert_eq!(h.undo(2), Some(1));
+        assert_eq!(h.undo(1), None);
+        assert_eq!(h.redo(1), Some(2));
+        assert_eq!(h.redo(2), Some(3));
+        assert_eq!(h.redo(3), None);
+        // and undo again
+        assert_eq!(h.undo(3), Some(2));
+    }
+
+    #[test]
+    fn test_push_clears_redo() {
+        let mut h = History::default();
+        h.push(1);
+        assert_eq!(h.undo(2), Some(1));
+        h.push(1);
+        assert_eq!(h.redo(5), None);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut h = History::default();
+        for i in 0..HISTORY_LIMIT + 5 {
+            h.push(i);
+        }
+        let mut count = 0;
+        while h.undo(0).is_some() {
+            count += 1;
+        }
+        assert_eq!(count, HISTORY_LIMIT);
+    }
+}
@@ -1,37 +1,53 @@
+mod alarm;
 mod app;
 mod common;
 mod config;
 mod constants;
 mod event;
 mod events;
+mod history;
 mod logging;
+mod notification;
+mod preset;
+mod schedule;
 
 mod args;
+mod bundle;
 mod duration;
 mod storage;
 mod terminal;
+mod urgency;
+mod wall_time;
 mod widgets;
 
 #[cfg(feature = "sound")]
 mod sound;
 
+#[cfg(test)]
+mod test_utils;
+
 use app::{App, FromAppArgs};
-use args::{Args, LOG_DIRECTORY_DEFAULT_MISSING_VALUE};
+use args::{Args, Command, LOG_DIRECTORY_DEFAULT_MISSING_VALUE};
+use bundle::Bundle;
 use clap::Parser;
-use color_eyre::Result;
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, eyre},
+};
 use config::Config;
+use std::fs;
 use std::path::PathBuf;
 use storage::{AppStorage, Storage};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // init `Config`
-    let cfg = Config::init()?;
-
     color_eyre::install()?;
 
     // get args given by CLI
     let args = Args::parse();
+
+    // init `Config`
+    let cfg = Config::init(args.data_dir.clone(), args.portable)?;
     // Note:
     // `log` arg can have three different values:
     // (1) not set => None
@@ -55,30 +71,101 @@ async fn main() -> Result<()> {
         logging::Logger::new(dir).init()?;
     }
 
-    let mut terminal = terminal::setup()?;
-    let events = events::Events::new();
+    // list sound devices only
+    #[cfg(feature = "sound")]
+    if args.sound_device.as_deref() == Some(sound::LIST_DEVICES) {
+        for name in sound::list_devices()? {
+            println!("{name}");
+        }
+        return Ok(());
+    }
 
     // check persistant storage
-    let storage = Storage::new(cfg.data_dir);
+    let storage = Storage::new(cfg.data_dir, args.instance.as_deref());
+
+    // run subcommands only
+    if let Some(command) = &args.command {
+        return run_command(command, &storage);
+    }
+
+    // lock is released by dropping it on exit.
+    // Note: It's taken before setting up the terminal to keep it untouched by any error.
+    let (_lock, lock_warning) = storage.lock()?;
     // option to reset previous stored data to `default`
-    let stg = if args.reset {
-        AppStorage::default()
+    let (stg, load_notice) = if args.reset {
+        (AppStorage::default(), None)
     } else {
-        storage.load().unwrap_or_default()
+        storage.load()
+    };
+    let notice = match (load_notice, lock_warning) {
+        (Some(a), Some(b)) => Some(format!("{a} {b}")),
+        (a, b) => a.or(b),
     };
 
-    let app_storage = App::from(FromAppArgs {
+    let mut terminal = terminal::setup()?;
+    let events = events::Events::new();
+
+    // terminal's panic hook has been set in `terminal::setup` before,
+    // which will be called after saving the storage
+    storage.set_panic_hook();
+
+    let result = App::from(FromAppArgs {
         args,
         stg,
         app_tx: events.get_app_event_tx(),
+        notice,
     })
-    .run(&mut terminal, events)
-    .await?
-    .to_storage();
+    .run(&mut terminal, events, &storage)
+    .await;
+
+    // restore terminal in any case before reporting errors
+    let teardown_result = terminal::teardown();
+
+    let app_storage = match result {
+        Ok(app) => app.to_storage(),
+        Err(err) => {
+            // e.g. drawing fails after closing the terminal (`SIGHUP`)
+            // so try to store latest known state at least
+            if let Some(data) = storage::take_latest() {
+                let _ = storage.save(data);
+            }
+            return Err(err);
+        }
+    };
     // store app state persistantly
     storage.save(app_storage)?;
 
-    terminal::teardown()?;
+    teardown_result?;
 
     Ok(())
 }
+
+/// Runs a subcommand (`export` or `import`) without starting the app
+fn run_command(command: &Command, storage: &Storage) -> Result<()> {
+    match command {
+        Command::Export { output } => {
+            let (data, notice) = storage.load();
+            if let Some(notice) = notice {
+                eprintln!("{notice}");
+            }
+            let json = serde_json::to_string_pretty(&Bundle::export(&data)?)?;
+            match output {
+                Some(path) => fs::write(path, json)?,
+                None => println!("{json}"),
+            }
+        }
+        Command::Import { file } => {
+            // a running app would overwrite imported data on quit
+            if let Some(holder) = storage.locked_by() {
+                return Err(eyre!(
+                    "Data is used by {holder}. Quit it before importing data."
+                ));
+            }
+            let data = Bundle::read(file)
+                .and_then(Bundle::import)
+                .wrap_err_with(|| format!("Failed to import {:?}", file))?;
+            storage.save(data)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,420 @@
+use std::{fmt, io, time::Duration};
+
+use crate::constants::APP_NAME;
+use clap::ValueEnum;
+use color_eyre::{
+    Report,
+    eyre::{Result, ensure, eyre},
+};
+use crossterm::{Command, execute};
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+/// Backend to send notifications with.
+/// Several backends can be combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum NotificationBackend {
+    /// Desktop notification (needs a notification daemon)
+    #[value(name = "desktop", alias = "d")]
+    Desktop,
+    /// Terminal bell (`BEL`)
+    #[value(name = "bell", alias = "b")]
+    Bell,
+    /// Terminal notification by using `OSC 9` (e.g. iTerm2, Windows Terminal, WezTerm)
+    #[value(name = "osc9")]
+    Osc9,
+    /// Terminal notification by using `OSC 777` (e.g. Ghostty, foot, rxvt-unicode)
+    #[value(name = "osc777")]
+    Osc777,
+    /// Flashing screen
+    #[value(name = "flash", alias = "f")]
+    Flash,
+}
+
+pub fn default_backends() -> Vec<NotificationBackend> {
+    vec![NotificationBackend::Desktop]
+}
+
+/// Kind of a finished clock to pick a notification template for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Display, Serialize, Deserialize)]
+pub enum NotificationKind {
+    #[value(name = "countdown")]
+    Countdown,
+    #[value(name = "timer")]
+    Timer,
+    #[value(name = "event")]
+    Event,
+    /// Pomodoro work phase
+    #[value(name = "work")]
+    Work,
+    /// Pomodoro pause phase
+    #[value(name = "pause")]
+    Pause,
+}
+
+impl NotificationKind {
+    /// Template used if no custom template has been defined.
+    /// Note: Messages of default templates are upper-cased.
+    fn default_template(&self) -> &'static str {
+        match self {
+            NotificationKind::Countdown => "Countdown {name} done!",
+            NotificationKind::Timer => "{name} stopped by reaching its maximum value.",
+            NotificationKind::Event => "Event {name} done!",
+            NotificationKind::Work | NotificationKind::Pause => "Countdown {name} done!",
+        }
+    }
+}
+
+/// Placeholders available in templates
+const PLACEHOLDERS: [&str; 5] = ["name", "duration", "round", "time", "next"];
+
+/// Values to replace placeholders of a template with.
+#[derive(Debug, Default)]
+pub struct NotificationContext {
+    /// `{name}`: Name of the clock (or title of an event)
+    pub name: String,
+    /// `{duration}`: Duration of the clock
+    pub duration: String,
+    /// `{round}`: Current round of pomodoro
+    pub round: String,
+    /// `{time}`: Local time the clock has been finished
+    pub time: String,
+    /// `{next}`: Next phase of pomodoro
+    pub next: String,
+}
+
+impl NotificationContext {
+    fn get(&self, placeholder: &str) -> Option<&str> {
+        match placeholder {
+            "name" => Some(&self.name),
+            "duration" => Some(&self.duration),
+            "round" => Some(&self.round),
+            "time" => Some(&self.time),
+            "next" => Some(&self.next),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces all placeholders (e.g. `{name}`) of a template by values of given `NotificationContext`.
+/// Unknown placeholders are kept as they are.
+fn render_template(template: &str, ctx: &NotificationContext) -> String {
+    let mut result = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match tail
+            .find('}')
+            .and_then(|end| ctx.get(&tail[1..end]).map(|value| (end, value)))
+        {
+            Some((end, value)) => {
+                result.push_str(value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Custom templates by `NotificationKind`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationTemplates {
+    pub countdown: Option<String>,
+    pub timer: Option<String>,
+    pub event: Option<String>,
+    pub work: Option<String>,
+    pub pause: Option<String>,
+}
+
+impl NotificationTemplates {
+    fn get(&self, kind: NotificationKind) -> Option<&String> {
+        match kind {
+            NotificationKind::Countdown => self.countdown.as_ref(),
+            NotificationKind::Timer => self.timer.as_ref(),
+            NotificationKind::Event => self.event.as_ref(),
+            NotificationKind::Work => self.work.as_ref(),
+            NotificationKind::Pause => self.pause.as_ref(),
+        }
+    }
+
+    /// Sets a template. An empty template removes a previous one to use the default template again.
+    pub fn set(&mut self, kind: NotificationKind, template: String) {
+        let template = Some(template).filter(|t| !t.is_empty());
+        match kind {
+            NotificationKind::Countdown => self.countdown = template,
+            NotificationKind::Timer => self.timer = template,
+            NotificationKind::Event => self.event = template,
+            NotificationKind::Work => self.work = template,
+            NotificationKind::Pause => self.pause = template,
+        }
+    }
+
+    /// Creates message by using a custom template or falls back to a default template.
+    pub fn message(&self, kind: NotificationKind, ctx: &NotificationContext) -> String {
+        match self.get(kind) {
+            Some(template) => render_template(template, ctx),
+            None => render_template(kind.default_template(), ctx).to_uppercase(),
+        }
+    }
+}
+
+/// Parses a template for a `NotificationKind`
+/// Format: "kind=template", e.g. "pause=Break over - round {round} starts"
+pub fn parse_notification_template(arg: &str) -> Result<(NotificationKind, String), Report> {
+    let (kind, template) = arg
+        .split_once('=')
+        .ok_or_else(|| eyre!("Missing '='. Expected format: 'kind=template'"))?;
+    let kind = NotificationKind::from_str(kind.trim(), true).map_err(|_| {
+        eyre!(
+            "Invalid kind: '{}'. Valid kinds: countdown, timer, event, work, pause",
+            kind.trim()
+        )
+    })?;
+    // check placeholders
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| eyre!("Missing '}}' in template: '{}'", template))?;
+        let placeholder = &rest[start + 1..start + end];
+        ensure!(
+            PLACEHOLDERS.contains(&placeholder),
+            "Unknown placeholder: '{{{}}}'. Valid placeholders: {}",
+            placeholder,
+            PLACEHOLDERS.map(|p| format!("{{{p}}}")).join(", ")
+        );
+        rest = &rest[start + end + 1..];
+    }
+    Ok((kind, template.to_owned()))
+}
+
+/// Urgency of desktop notifications
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum NotificationUrgency {
+    #[value(name = "low")]
+    Low,
+    #[default]
+    #[value(name = "normal")]
+    Normal,
+    #[value(name = "critical")]
+    Critical,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl From<NotificationUrgency> for notify_rust::Urgency {
+    fn from(value: NotificationUrgency) -> Self {
+        match value {
+            NotificationUrgency::Low => notify_rust::Urgency::Low,
+            NotificationUrgency::Normal => notify_rust::Urgency::Normal,
+            NotificationUrgency::Critical => notify_rust::Urgency::Critical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    #[serde(default)]
+    pub templates: NotificationTemplates,
+    #[serde(default)]
+    pub urgency: NotificationUrgency,
+    /// `None`: default timeout of notification server, `Duration::ZERO`: never expires
+    #[serde(default)]
+    pub timeout: Option<Duration>,
+    /// Name or path of an icon
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub app_name: Option<String>,
+}
+
+impl NotificationSettings {
+    pub fn get_app_name(&self) -> &str {
+        self.app_name.as_deref().unwrap_or(APP_NAME)
+    }
+}
+
+/// Sends a notification by given backend.
+/// Note: `NotificationBackend::Flash` has to be handled by `App` itself, it's ignored here.
+pub fn send(
+    backend: NotificationBackend,
+    settings: &NotificationSettings,
+    message: &str,
+) -> Result<()> {
+    let app_name = settings.get_app_name();
+    match backend {
+        NotificationBackend::Desktop => {
+            let mut notification = notify_rust::Notification::new();
+            notification.appname(app_name).summary(message);
+            #[cfg(all(unix, not(target_os = "macos")))]
+            notification.urgency(settings.urgency.into());
+            if let Some(timeout) = settings.timeout {
+                notification.timeout(timeout);
+            }
+            if let Some(icon) = &settings.icon {
+                notification.icon(icon);
+            }
+            notification.show()?;
+        }
+        NotificationBackend::Bell => execute!(io::stdout(), Bell)?,
+        NotificationBackend::Osc9 => execute!(io::stdout(), Osc9Notification(message))?,
+        NotificationBackend::Osc777 => {
+            execute!(io::stdout(), Osc777Notification(app_name, message))?
+        }
+        NotificationBackend::Flash => {}
+    }
+    Ok(())
+}
+
+/// Removes all characters which might break an escape sequence.
+/// Semicolons are replaced too, since `OSC 777` uses them to separate its fields.
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
+
+/// Rings the terminal bell
+struct Bell;
+
+impl Command for Bell {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x07")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Notification by `OSC 9;{body}`
+struct Osc9Notification<'a>(&'a str);
+
+impl Command for Osc9Notification<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1B]9;{}\x07", sanitize(self.0))
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Notification by `OSC 777;notify;{title};{body}`
+struct Osc777Notification<'a>(&'a str, &'a str);
+
+impl Command for Osc777Notification<'_> {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(
+            f,
+            "\x1B]777;notify;{};{}\x07",
+            sanitize(self.0),
+            sanitize(self.1)
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::to_ansi;
+
+    #[test]
+    fn test_notification_ansi() {
+        assert_eq!(to_ansi(Bell), "\x07");
+        assert_eq!(
+            to_ansi(Osc9Notification("COUNTDOWN DONE!")),
+            "\x1B]9;COUNTDOWN DONE!\x07"
+        );
+        assert_eq!(
+            to_ansi(Osc777Notification("timr-tui", "COUNTDOWN DONE!")),
+            "\x1B]777;notify;timr-tui;COUNTDOWN DONE!\x07"
+        );
+    }
+
+    fn context() -> NotificationContext {
+        NotificationContext {
+            name: "Pause".into(),
+            duration: "5:00".into(),
+            round: "4".into(),
+            time: "14:30".into(),
+            next: "Work".into(),
+        }
+    }
+
+    #[test]
+    fn test_render_template() {
+        let ctx = context();
+        assert_eq!(
+            render_template("Break over - round {round} starts", &ctx),
+            "Break over - round 4 starts"
+        );
+        assert_eq!(
+            render_template("{name} ({duration}) done at {time}, next: {next}", &ctx),
+            "Pause (5:00) done at 14:30, next: Work"
+        );
+        // unknown or incomplete placeholders are kept
+        assert_eq!(render_template("{unknown} {name", &ctx), "{unknown} {name");
+    }
+
+    #[test]
+    fn test_message() {
+        let ctx = context();
+        let mut templates = NotificationTemplates::default();
+        // default template, upper-cased
+        assert_eq!(
+            templates.message(NotificationKind::Pause, &ctx),
+            "COUNTDOWN PAUSE DONE!"
+        );
+        // custom template
+        templates.set(NotificationKind::Pause, "Round {round} starts".into());
+        assert_eq!(
+            templates.message(NotificationKind::Pause, &ctx),
+            "Round 4 starts"
+        );
+        // other kinds are not affected
+        assert_eq!(
+            templates.message(NotificationKind::Event, &ctx),
+            "EVENT PAUSE DONE!"
+        );
+        // empty template removes custom template
+        templates.set(NotificationKind::Pause, "".into());
+        assert_eq!(templates.pause, None);
+    }
+
+    #[test]
+    fn test_parse_notification_template() {
+        assert_eq!(
+            parse_notification_template("pause=Round {round} starts").unwrap(),
+            (NotificationKind::Pause, "Round {round} starts".into())
+        );
+        assert_eq!(
+            parse_notification_template("Countdown=a=b").unwrap(),
+            (NotificationKind::Countdown, "a=b".into())
+        );
+        assert!(parse_notification_template("pause").is_err()); // missing '='
+        assert!(parse_notification_template("lunch=done").is_err()); // invalid kind
+        assert!(parse_notification_template("work={unknown}").is_err()); // unknown placeholder
+        assert!(parse_notification_template("work={name").is_err()); // missing '}'
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("a;b"), "a,b");
+        assert_eq!(sanitize("a\x07b\x1B"), "ab");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::{
+    common::Content,
+    duration::{self, DurationEx},
+};
+use color_eyre::{
+    Report,
+    eyre::{ensure, eyre},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Max. number of recently used durations to remember
+pub const RECENT_LIMIT: usize = 5;
+
+/// Durations of a preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresetValue {
+    Countdown(Duration),
+    Pomodoro { work: Duration, pause: Duration },
+}
+
+impl PresetValue {
+    /// Content the value can be applied to
+    pub fn content(&self) -> Content {
+        match self {
+            PresetValue::Countdown(_) => Content::Countdown,
+            PresetValue::Pomodoro { .. } => Content::Pomodoro,
+        }
+    }
+}
+
+/// Formats: `duration` (countdown) or `work/pause` (pomodoro), e.g. `3:00` or `50:00/10:00`
+impl fmt::Display for PresetValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresetValue::Countdown(d) => write!(f, "{}", DurationEx::from(*d)),
+            PresetValue::Pomodoro { work, pause } => write!(
+                f,
+                "{}/{}",
+                DurationEx::from(*work),
+                DurationEx::from(*pause)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub value: PresetValue,
+}
+
+/// Named presets and recently used durations
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Presets {
+    /// Presets in order of their definition
+    pub presets: Vec<Preset>,
+    /// Recently used durations, latest first
+    pub recent: Vec<PresetValue>,
+}
+
+impl Presets {
+    pub fn get(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|p| p.name == name)
+    }
+
+    /// Adds or replaces a preset. `None` removes it.
+    pub fn set(&mut self, name: String, value: Option<PresetValue>) {
+        match (self.presets.iter_mut().find(|p| p.name == name), value) {
+            (Some(preset), Some(value)) => preset.value = value,
+            (None, Some(value)) => self.presets.push(Preset { name, value }),
+            (_, None) => self.presets.retain(|p| p.name != name),
+        }
+    }
+
+    /// Remembers a used duration as latest one
+    pub fn add_recent(&mut self, value: PresetValue) {
+        self.recent.retain(|v| *v != value);
+        self.recent.insert(0, value);
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Presets of a `Content` followed by recently used durations.
+    /// Recent durations of a preset are skipped.
+    pub fn items(&self, content: Content) -> Vec<(Option<&str>, PresetValue)> {
+        let presets = self
+            .presets
+            .iter()
+            .filter(|p| p.value.content() == content)
+            .map(|p| (Some(p.name.as_str()), p.value));
+        let recent = self
+            .recent
+            .iter()
+            .filter(|v| v.content() == content && !self.presets.iter().any(|p| p.value == **v))
+            .map(|v| (None, *v));
+        presets.chain(recent).collect()
+    }
+}
+
+/// Parses a `PresetValue`. Formats: see `PresetValue` (durations: see `duration::parse_long_duration`)
+pub fn parse_preset_value(s: &str) -> Result<PresetValue, Report> {
+    let parse = |d: &str| {
+        ensure!(!d.trim().is_empty(), "Missing duration");
+        duration::parse_long_duration(d)
+    };
+    match s.split_once('/') {
+        Some((work, pause)) => Ok(PresetValue::Pomodoro {
+            work: parse(work)?,
+            pause: parse(pause)?,
+        }),
+        None => Ok(PresetValue::Countdown(parse(s)?)),
+    }
+}
+
+/// Parses a named preset. An empty value removes a preset.
+/// Format: "name=duration" or "name=work/pause", e.g. "tea=3:00" or "deep=50:00/10:00"
+pub fn parse_preset(arg: &str) -> Result<(String, Option<PresetValue>), Report> {
+    let (name, value) = arg.split_once('=').ok_or_else(|| {
+        eyre!("Missing '='. Expected format: 'name=duration' or 'name=work/pause'")
+    })?;
+    let name = name.trim();
+    ensure!(!name.is_empty(), "Missing name of preset");
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok((name.to_owned(), None));
+    }
+    let value = parse_preset_value(value)
+        .map_err(|err| eyre!("Invalid value of preset '{}': {}", name, err))?;
+    Ok((name.to_owned(), Some(value)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TEA: PresetValue = PresetValue::Countdown(Duration::from_secs(180));
+    const DEEP: PresetValue = PresetValue::Pomodoro {
+        work: Duration::from_secs(3000),
+        pause: Duration::from_secs(600),
+    };
+
+    #[test]
+    fn test_parse_preset() {
+        assert_eq!(parse_preset("tea=3:00").unwrap(), ("tea".into(), Some(TEA)));
+        assert_eq!(
+            parse_preset(" deep = 50:00/10:00 ").unwrap(),
+            ("deep".into(), Some(DEEP))
+        );
+        assert_eq!(
+            parse_preset("long=1d 2:00").unwrap().1,
+            Some(PresetValue::Countdown(Duration::from_secs(86400 + 120)))
+        );
+        // remove
+        assert_eq!(parse_preset("tea=").unwrap(), ("tea".into(), None));
+        // errors
+        assert!(parse_preset("tea").is_err());
+        assert!(parse_preset("=3:00").is_err());
+        assert!(parse_preset("tea=3:99").is_err());
+        assert!(parse_preset("deep=50:00/").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(TEA.to_string(), "3:00");
+        assert_eq!(DEEP.to_string(), "50:00/10:00");
+        // round trip
+        assert_eq!(parse_preset_value(&DEEP.to_string()).unwrap(), DEEP);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut presets = Presets::default();
+        presets.set("tea".into(), Some(TEA));
+        presets.set("deep".into(), Some(DEEP));
+        assert_eq!(presets.get("tea").map(|p| p.value), Some(TEA));
+        // replace
+        presets.set("tea".into(), Some(DEEP));
+        assert_eq!(presets.get("tea").map(|p| p.value), Some(DEEP));
+        assert_eq!(presets.presets.len(), 2);
+        // remove
+        presets.set("tea".into(), None);
+        assert!(presets.get("tea").is_none());
+        assert_eq!(presets.presets.len(), 1);
+    }
+
+    #[test]
+    fn test_recent() {
+        let mut presets = Presets::default();
+        for secs in 1..=RECENT_LIMIT as u64 + 2 {
+            presets.add_recent(PresetValue::Countdown(Duration::from_secs(secs)));
+        }
+        assert_eq!(presets.recent.len(), RECENT_LIMIT);
+        // latest first, no duplicates
+        presets.add_recent(PresetValue::Countdown(Duration::from_secs(5)));
+        assert_eq!(
+            presets.recent[0],
+            PresetValue::Countdown(Duration::from_secs(5))
+        );
+        assert_eq!(presets.recent.len(), RECENT_LIMIT);
+    }
+
+    #[test]
+    fn test_items() {
+        let mut presets = Presets::default();
+        presets.set("tea".into(), Some(TEA));
+        presets.set("deep".into(), Some(DEEP));
+        let other = PresetValue::Countdown(Duration::from_secs(60));
+        presets.add_recent(other);
+        presets.add_recent(TEA);
+        presets.add_recent(DEEP);
+        assert_eq!(
+            presets.items(Content::Countdown),
+            vec![(Some("tea"), TEA), (None, other)]
+        );
+        assert_eq!(presets.items(Content::Pomodoro), vec![(Some("deep"), DEEP)]);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{common::Content, duration::DurationEx};
+use color_eyre::{Report, eyre::ensure};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+use time::OffsetDateTime;
+
+/// Countdown or pomodoro clock armed to start automatically at a given time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledStart {
+    /// Content of the clock to start
+    pub content: Content,
+    /// Time to start the clock
+    pub at: SystemTime,
+    /// Time before start to send a notification
+    pub pre_start: Option<Duration>,
+    /// Flag to send the notification once only
+    pub pre_start_notified: bool,
+}
+
+impl ScheduledStart {
+    pub fn new(
+        content: Content,
+        at: SystemTime,
+        pre_start: Option<Duration>,
+    ) -> Result<Self, Report> {
+        ensure!(
+            Self::is_supported(content),
+            "A scheduled start is supported by countdown and pomodoro only."
+        );
+        Ok(Self {
+            content,
+            at,
+            pre_start,
+            pre_start_notified: false,
+        })
+    }
+
+    pub fn is_supported(content: Content) -> bool {
+        matches!(content, Content::Countdown | Content::Pomodoro)
+    }
+
+    /// Time left to start. `Duration::ZERO` if it's due.
+    pub fn remaining(&self, now: OffsetDateTime) -> Duration {
+        self.at.duration_since(now.into()).unwrap_or_default()
+    }
+
+    /// Time passed since it has been due (e.g. while the app was closed)
+    pub fn overdue(&self, now: OffsetDateTime) -> Duration {
+        SystemTime::from(now)
+            .duration_since(self.at)
+            .unwrap_or_default()
+    }
+
+    pub fn is_due(&self, now: OffsetDateTime) -> bool {
+        self.remaining(now).is_zero()
+    }
+
+    /// Checks whether it's time to send the pre-start notification
+    pub fn needs_pre_start_notification(&self, now: OffsetDateTime) -> bool {
+        !self.pre_start_notified
+            && !self.is_due(now)
+            && self.pre_start.is_some_and(|d| self.remaining(now) <= d)
+    }
+
+    /// Message of an armed clock, e.g. "countdown starts in 4:59"
+    pub fn message(&self, now: OffsetDateTime) -> String {
+        // round up to show `1` (not `0`) during last second
+        let remaining = self.remaining(now);
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let name = match self.content {
+            Content::Countdown => "countdown",
+            Content::Pomodoro => "pomodoro",
+            Content::Timer | Content::Event | Content::LocalTime => {
+                unreachable!("checked by `ScheduledStart::new`")
+            }
+        };
+        format!(
+            "{name} starts in {}",
+            DurationEx::from(Duration::from_secs(secs))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use time::macros::datetime;
+
+    const NOW: OffsetDateTime = datetime!(2026-10-19 14:00:00 UTC);
+
+    fn at(secs: i64) -> SystemTime {
+        (NOW + time::Duration::seconds(secs)).into()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ScheduledStart::new(Content::Countdown, at(60), None).is_ok());
+        assert!(ScheduledStart::new(Content::Pomodoro, at(60), None).is_ok());
+        assert!(ScheduledStart::new(Content::Timer, at(60), None).is_err());
+        assert!(ScheduledStart::new(Content::Event, at(60), None).is_err());
+        assert!(ScheduledStart::new(Content::LocalTime, at(60), None).is_err());
+    }
+
+    #[test]
+    fn test_remaining() {
+        let start = ScheduledStart::new(Content::Countdown, at(600), None).unwrap();
+        assert_eq!(start.remaining(NOW), Duration::from_secs(600));
+        assert_eq!(start.overdue(NOW), Duration::ZERO);
+        assert!(!start.is_due(NOW));
+        assert_eq!(start.message(NOW), "countdown starts in 10:00");
+
+        let start = ScheduledStart::new(Content::Pomodoro, at(-30), None).unwrap();
+        assert_eq!(start.remaining(NOW), Duration::ZERO);
+        assert_eq!(start.overdue(NOW), Duration::from_secs(30));
+        assert!(start.is_due(NOW));
+    }
+
+    #[test]
+    fn test_pre_start_notification() {
+        let mut start =
+            ScheduledStart::new(Content::Countdown, at(600), Some(Duration::from_secs(300)))
+                .unwrap();
+        assert!(!start.needs_pre_start_notification(NOW));
+        let later = NOW + time::Duration::seconds(300);
+        assert!(start.needs_pre_start_notification(later));
+        start.pre_start_notified = true;
+        assert!(!start.needs_pre_start_notification(later));
+
+        // no notification
+        let start = ScheduledStart::new(Content::Countdown, at(60), None).unwrap();
+        assert!(!start.needs_pre_start_notification(NOW));
+    }
+}
@@ -1,9 +1,20 @@
-use rodio::{Decoder, DeviceSinkBuilder, MixerDeviceSink, Source, source::Buffered};
+use crate::common::Tone;
+use rodio::{
+    Decoder, DeviceSinkBuilder, DeviceTrait, MixerDeviceSink, Sample, Source,
+    buffer::SamplesBuffer,
+    cpal::{self, traits::HostTrait},
+    source::Buffered,
+};
+use std::collections::HashMap;
+use std::f32::consts::TAU;
 use std::fs::File;
 use std::io::BufReader;
+use std::num::NonZero;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tracing::{error, warn};
 
 #[derive(Debug, Error)]
 pub enum SoundError {
@@ -35,25 +46,288 @@ pub fn validate_sound_file(path: &PathBuf) -> Result<&PathBuf, SoundError> {
     Ok(path)
 }
 
+/// Events to play a sound for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundKind {
+    /// Any other event (e.g. a timer reaching its maximum value)
+    Default,
+    CountdownDone,
+    WorkDone,
+    PauseDone,
+    EventDone,
+    /// Countdown or pomodoro clock is about to end
+    PreAlert,
+    /// Soft tick each second of a running clock (built-in only)
+    Tick,
+    /// Beep of last seconds ("3-2-1") of a running countdown (built-in only)
+    CountdownBeep,
+}
+
+/// Paths to sound files by `SoundKind`.
+/// A missing path falls back to `default`.
+#[derive(Debug, Clone, Default)]
+pub struct SoundPaths {
+    pub default: Option<PathBuf>,
+    pub countdown_done: Option<PathBuf>,
+    pub work_done: Option<PathBuf>,
+    pub pause_done: Option<PathBuf>,
+    pub event_done: Option<PathBuf>,
+    pub pre_alert: Option<PathBuf>,
+}
+
+impl SoundPaths {
+    fn into_iter(self) -> impl Iterator<Item = (SoundKind, PathBuf)> {
+        [
+            (SoundKind::Default, self.default),
+            (SoundKind::CountdownDone, self.countdown_done),
+            (SoundKind::WorkDone, self.work_done),
+            (SoundKind::PauseDone, self.pause_done),
+            (SoundKind::EventDone, self.event_done),
+            (SoundKind::PreAlert, self.pre_alert),
+        ]
+        .into_iter()
+        .filter_map(|(kind, path)| path.map(|p| (kind, p)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clone().into_iter().next().is_none()
+    }
+}
+
+const TONE_SAMPLE_RATE: u32 = 44_100;
+const TONE_VOLUME: f32 = 0.5;
+/// Time to fade in/out a tone to avoid clicks
+const TONE_FADE: f32 = 0.005;
+
+impl Tone {
+    fn samples(&self) -> Vec<Sample> {
+        let ms = Duration::from_millis;
+        match self {
+            Tone::Beep => sine(ms(300), |_| 880.0, |_| 1.0),
+            Tone::Chime => [
+                sine(ms(400), |_| 1046.5, |t| (-t * 6.0).exp()),
+                sine(ms(800), |_| 784.0, |t| (-t * 4.0).exp()),
+            ]
+            .concat(),
+            Tone::TripleBeep => [sine(ms(120), |_| 1000.0, |_| 1.0), silence(ms(80))]
+                .concat()
+                .repeat(3),
+            Tone::RisingAlarm => [
+                sine(ms(400), |t| 440.0 + t * 2200.0, |_| 1.0),
+                silence(ms(100)),
+            ]
+            .concat()
+            .repeat(3),
+            Tone::Off => Vec::new(),
+        }
+    }
+
+    /// `None` for `Tone::Off`
+    fn buffer(&self) -> Option<SamplesBuffer> {
+        (*self != Tone::Off).then(|| samples_buffer(self.samples()))
+    }
+}
+
+fn samples_buffer(samples: Vec<Sample>) -> SamplesBuffer {
+    SamplesBuffer::new(
+        NonZero::<u16>::MIN, // mono
+        NonZero::new(TONE_SAMPLE_RATE).unwrap_or(NonZero::<u32>::MIN),
+        samples,
+    )
+}
+
+/// Soft and short click
+fn tick_samples() -> Vec<Sample> {
+    sine(
+        Duration::from_millis(15),
+        |_| 1500.0,
+        |t| 0.3 * (-t * 200.0).exp(),
+    )
+}
+
+/// Short beep
+fn countdown_beep_samples() -> Vec<Sample> {
+    sine(Duration::from_millis(100), |_| 1000.0, |_| 0.8)
+}
+
+/// Generates samples of a sine wave.
+/// Frequency and envelope (0.0-1.0) are functions of time (in seconds).
+fn sine(
+    duration: Duration,
+    freq: impl Fn(f32) -> f32,
+    envelope: impl Fn(f32) -> f32,
+) -> Vec<Sample> {
+    let sample_rate = TONE_SAMPLE_RATE as f32;
+    let length = duration.as_secs_f32();
+    let mut phase = 0.0_f32;
+    (0..sample_count(duration))
+        .map(|i| {
+            let t = i as f32 / sample_rate;
+            let fade = (t / TONE_FADE)
+                .min((length - t) / TONE_FADE)
+                .clamp(0.0, 1.0);
+            phase = (phase + TAU * freq(t) / sample_rate) % TAU;
+            (phase.sin() * envelope(t) * fade * TONE_VOLUME) as Sample
+        })
+        .collect()
+}
+
+fn silence(duration: Duration) -> Vec<Sample> {
+    vec![0.0; sample_count(duration)]
+}
+
+fn sample_count(duration: Duration) -> usize {
+    (duration.as_micros() * TONE_SAMPLE_RATE as u128 / 1_000_000) as usize
+}
+
+type SoundBuffer = Arc<Buffered<Decoder<BufReader<File>>>>;
+
+/// Cached source to play
+#[derive(Clone)]
+enum SoundSource {
+    File(SoundBuffer),
+    Tone(SamplesBuffer),
+}
+
+/// Value of `--sound-device` to list all output devices
+pub const LIST_DEVICES: &str = "list";
+
+/// Names of all available output devices
+pub fn list_devices() -> Result<Vec<String>, SoundError> {
+    let devices = cpal::default_host()
+        .output_devices()
+        .map_err(|e| SoundError::OutputStream(e.to_string()))?;
+    Ok(devices
+        .filter_map(|d| d.description().ok())
+        .map(|d| d.name().to_owned())
+        .collect())
+}
+
+/// Opens output stream of a device by its name.
+/// Falls back to default device if no name is given or no device is found.
+fn open_sink(device_name: Option<&str>) -> Result<MixerDeviceSink, SoundError> {
+    if let Some(name) = device_name {
+        let device = cpal::default_host()
+            .output_devices()
+            .map_err(|e| SoundError::OutputStream(e.to_string()))?
+            .find(|d| d.description().is_ok_and(|desc| desc.name() == name));
+        match device {
+            Some(device) => {
+                return DeviceSinkBuilder::from_device(device)
+                    .and_then(|builder| builder.open_stream())
+                    .map_err(|e| SoundError::OutputStream(e.to_string()));
+            }
+            None => error!("Sound device not found: {name}. Using default device."),
+        }
+    }
+    DeviceSinkBuilder::open_default_sink()
+        .map_err(|e: rodio::DeviceSinkError| SoundError::OutputStream(e.to_string()))
+}
+
 pub struct Sound {
-    buffer: Arc<Buffered<Decoder<BufReader<File>>>>,
+    sources: HashMap<SoundKind, SoundSource>,
+    /// Tone to play if no file is given (`None` for `Tone::Off`)
+    tone: Option<SamplesBuffer>,
     stream: MixerDeviceSink,
 }
 
 impl Sound {
-    pub fn new(path: PathBuf) -> Result<Self, SoundError> {
-        let stream = DeviceSinkBuilder::open_default_sink()
-            .map_err(|e: rodio::DeviceSinkError| SoundError::OutputStream(e.to_string()))?;
+    /// Creates `Sound` by decoding all given files.
+    /// A file which can't be decoded is skipped, its fallback is played instead (see `play`).
+    pub fn new(
+        paths: SoundPaths,
+        tone: Tone,
+        device_name: Option<&str>,
+    ) -> Result<Self, SoundError> {
+        let stream = open_sink(device_name)?;
 
-        let file = File::open(&path).map_err(|e| SoundError::File(e.to_string()))?;
-        let decoder = Decoder::try_from(file).map_err(|e| SoundError::Decoder(e.to_string()))?;
-        let buffer = Arc::new(decoder.buffered());
+        // decode each file once, even if it's used by different kinds
+        let mut cache: HashMap<PathBuf, Option<SoundBuffer>> = HashMap::new();
+        let mut sources = HashMap::new();
+        for (kind, path) in paths.into_iter() {
+            let buffer = cache
+                .entry(path)
+                .or_insert_with_key(|path| {
+                    decode(path)
+                        .inspect_err(|err| {
+                            warn!(
+                                "Sound file {:?} is skipped, its fallback is played instead: {}",
+                                path, err
+                            )
+                        })
+                        .ok()
+                })
+                .clone();
+            if let Some(buffer) = buffer {
+                sources.insert(kind, SoundSource::File(buffer));
+            }
+        }
+        // built-in sounds
+        sources.insert(
+            SoundKind::Tick,
+            SoundSource::Tone(samples_buffer(tick_samples())),
+        );
+        sources.insert(
+            SoundKind::CountdownBeep,
+            SoundSource::Tone(samples_buffer(countdown_beep_samples())),
+        );
 
-        Ok(Self { buffer, stream })
+        Ok(Self {
+            sources,
+            tone: tone.buffer(),
+            stream,
+        })
     }
 
-    pub fn play(&self) -> Result<(), SoundError> {
-        self.stream.mixer().add((*self.buffer).clone());
+    /// Plays sound of given `SoundKind` with a volume (0.0-1.0).
+    /// Falls back to sound of `SoundKind::Default` or to a `Tone` (if it's not `Tone::Off`).
+    pub fn play(&self, kind: SoundKind, volume: f32) -> Result<(), SoundError> {
+        let mixer = self.stream.mixer();
+        match self
+            .sources
+            .get(&kind)
+            .or_else(|| self.sources.get(&SoundKind::Default))
+        {
+            Some(SoundSource::File(buffer)) => mixer.add((**buffer).clone().amplify(volume)),
+            Some(SoundSource::Tone(buffer)) => mixer.add(buffer.clone().amplify(volume)),
+            None => {
+                if let Some(buffer) = &self.tone {
+                    mixer.add(buffer.clone().amplify(volume));
+                }
+            }
+        }
         Ok(())
     }
 }
+
+fn decode(path: &PathBuf) -> Result<SoundBuffer, SoundError> {
+    let file = File::open(path).map_err(|e| SoundError::File(e.to_string()))?;
+    let decoder = Decoder::try_from(file).map_err(|e| SoundError::Decoder(e.to_string()))?;
+    Ok(Arc::new(decoder.buffered()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_samples() {
+        let rate = TONE_SAMPLE_RATE as usize;
+        // 300ms
+        assert_eq!(Tone::Beep.samples().len(), rate * 3 / 10);
+        // 3 x (120ms + 80ms)
+        assert_eq!(Tone::TripleBeep.samples().len(), rate * 6 / 10);
+        // all samples within volume, starting silent (faded in)
+        for tone in [Tone::Beep, Tone::Chime, Tone::TripleBeep, Tone::RisingAlarm] {
+            let samples = tone.samples();
+            assert_eq!(samples[0], 0.0);
+            assert!(samples.iter().all(|s| s.abs() <= TONE_VOLUME));
+        }
+        assert!(Tone::Off.samples().is_empty());
+        // built-in sounds
+        for samples in [tick_samples(), countdown_beep_samples()] {
+            assert!(!samples.is_empty());
+            assert!(samples.iter().all(|s| s.abs() <= TONE_VOLUME));
+        }
+    }
+}
@@ -1,48 +1,95 @@
+mod migrations;
+
 use crate::{
-    common::{AppTimeFormat, Content, Style, Toggle},
+    alarm::AlarmSettings,
+    common::{AppTimeFormat, Content, ExtendSettings, QuitRunning, Style, Toggle, Tone},
     event::Event,
+    notification::{self, NotificationBackend, NotificationKind, NotificationSettings},
+    preset::Presets,
+    schedule::ScheduledStart,
+    urgency::UrgencyThresholds,
     widgets::pomodoro::Mode as PomodoroMode,
 };
 use color_eyre::eyre::Result;
-use serde::{Deserialize, Deserializer, Serialize};
-use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
-
-fn deserialize_app_time_format<'de, D>(deserializer: D) -> Result<AppTimeFormat, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s = String::deserialize(deserializer)?;
-    match s.as_str() {
-        // Hidden is deprecated - use `default` value instead
-        "Hidden" => Ok(AppTimeFormat::default()),
-        _ => s.parse().map_err(serde::de::Error::custom),
-    }
+use serde::{Deserialize, Serialize};
+use std::fs::{self, TryLockError};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tracing::error;
+
+/// Current version of `AppStorage`
+pub const VERSION: u32 = 4;
+
+pub const MAX_VOLUME: u8 = 100;
+
+pub const DEFAULT_AUTOSAVE: Duration = Duration::from_secs(30);
+
+/// Clocks to keep running while the app is closed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunningClocks {
+    /// Time the app has been closed
+    pub since: SystemTime,
+    pub countdown: bool,
+    pub timer: bool,
+    pub pomodoro: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Data stored persistently.
+/// Note: Any change of its fields needs a new `version` incl. a migration (see `migrations::MIGRATIONS`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppStorage {
+    /// Version of schema
+    pub version: u32,
     pub content: Content,
     pub show_menu: bool,
-    #[serde(default)]
     pub vim: Toggle,
     pub notification: Toggle,
+    pub notification_backends: Vec<NotificationBackend>,
+    pub notification_settings: NotificationSettings,
     pub blink: Toggle,
-    #[serde(deserialize_with = "deserialize_app_time_format")]
+    pub alarm: Toggle,
+    pub alarm_settings: AlarmSettings,
+    pub extend_settings: ExtendSettings,
+    /// Named presets and recently used durations
+    pub presets: Presets,
+    /// Min. running time of a clock to confirm its reset
+    pub confirm_reset: Duration,
+    pub quit_running: QuitRunning,
+    pub running_clocks: Option<RunningClocks>,
+    /// Clock armed to start automatically
+    pub scheduled_start: Option<ScheduledStart>,
+    /// Interval to save data while running. `Duration::ZERO` means no autosave.
+    pub autosave: Duration,
+    /// Volume of sounds (0-100)
+    pub volume: u8,
+    /// Name of output device to play sounds with
+    pub sound_device: Option<String>,
+    /// Built-in tone to play if no sound file is given
+    pub sound_tone: Tone,
+    /// Clock types to play a tick each second
+    pub tick_sound: Vec<NotificationKind>,
+    /// Clock types to beep during last seconds
+    pub countdown_beep: Vec<NotificationKind>,
+    pub urgency: Toggle,
+    pub urgency_thresholds: UrgencyThresholds,
+    pub terminal_title: Toggle,
+    pub terminal_progress: Toggle,
     pub app_time_format: AppTimeFormat,
     pub style: Style,
     pub with_decis: bool,
     pub pomodoro_mode: PomodoroMode,
     pub pomodoro_count: u64,
     // pomodoro -> work
-    pub inital_value_work: Duration,
+    pub initial_value_work: Duration,
     pub current_value_work: Duration,
     // pomodoro -> pause
-    pub inital_value_pause: Duration,
+    pub initial_value_pause: Duration,
     pub current_value_pause: Duration,
     // countdown
-    pub inital_value_countdown: Duration,
+    pub initial_value_countdown: Duration,
     pub current_value_countdown: Duration,
     pub elapsed_value_countdown: Duration,
     // timer
@@ -59,24 +106,45 @@ impl Default for AppStorage {
         const DEFAULT_PAUSE: Duration = Duration::from_secs(60 * 5); /* 5min */
         const DEFAULT_COUNTDOWN: Duration = Duration::from_secs(60 * 10); /* 10min */
         AppStorage {
+            version: VERSION,
             content: Content::default(),
             show_menu: true,
             vim: Toggle::Off,
             notification: Toggle::Off,
+            notification_backends: notification::default_backends(),
+            notification_settings: NotificationSettings::default(),
             blink: Toggle::Off,
+            alarm: Toggle::Off,
+            alarm_settings: AlarmSettings::default(),
+            extend_settings: ExtendSettings::default(),
+            presets: Presets::default(),
+            confirm_reset: Duration::ZERO,
+            quit_running: QuitRunning::default(),
+            running_clocks: None,
+            scheduled_start: None,
+            autosave: DEFAULT_AUTOSAVE,
+            volume: MAX_VOLUME,
+            sound_device: None,
+            sound_tone: Tone::default(),
+            tick_sound: Vec::new(),
+            countdown_beep: Vec::new(),
+            urgency: Toggle::Off,
+            urgency_thresholds: UrgencyThresholds::default(),
+            terminal_title: Toggle::Off,
+            terminal_progress: Toggle::Off,
             app_time_format: AppTimeFormat::default(),
             style: Style::default(),
             with_decis: false,
             pomodoro_mode: PomodoroMode::Work,
             pomodoro_count: 1,
             // pomodoro -> work
-            inital_value_work: DEFAULT_WORK,
+            initial_value_work: DEFAULT_WORK,
             current_value_work: DEFAULT_WORK,
             // pomodoro -> pause
-            inital_value_pause: DEFAULT_PAUSE,
+            initial_value_pause: DEFAULT_PAUSE,
             current_value_pause: DEFAULT_PAUSE,
             // countdown
-            inital_value_countdown: DEFAULT_COUNTDOWN,
+            initial_value_countdown: DEFAULT_COUNTDOWN,
             current_value_countdown: DEFAULT_COUNTDOWN,
             elapsed_value_countdown: Duration::ZERO,
             // timer
@@ -89,28 +157,364 @@ impl Default for AppStorage {
     }
 }
 
+/// Latest known `AppStorage` to save in case of a panic
+static LATEST: Mutex<Option<AppStorage>> = Mutex::new(None);
+
+/// Updates latest known `AppStorage`, which is saved by the panic hook (see `Storage::set_panic_hook`)
+pub fn set_latest(data: AppStorage) {
+    if let Ok(mut latest) = LATEST.try_lock() {
+        *latest = Some(data);
+    }
+}
+
+/// Takes latest known `AppStorage`.
+/// Note: `try_lock` to avoid a deadlock if a panic happens while `LATEST` is locked.
+pub fn take_latest() -> Option<AppStorage> {
+    LATEST.try_lock().ok().and_then(|mut latest| latest.take())
+}
+
+/// Converts data of any previous `version` by migrating it
+pub fn from_value(value: serde_json::Value) -> Result<AppStorage> {
+    let data = serde_json::from_value(migrations::migrate(value)?)?;
+    Ok(data)
+}
+
+/// Validates name of an instance to be used as part of file names
+pub fn parse_instance(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err("Name must not be empty".into());
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid name '{value}'. Allowed characters: 'a-z', 'A-Z', '0-9', '-', '_'."
+        ));
+    }
+    Ok(value.into())
+}
+
+#[derive(Clone)]
 pub struct Storage {
     data_dir: PathBuf,
+    /// Base name of all files: `app` or `app-{instance}`
+    file_name: String,
+    /// Flag to keep a backup once per session only (see `save`)
+    backup_done: Arc<AtomicBool>,
 }
 
 impl Storage {
-    pub fn new(data_dir: PathBuf) -> Self {
-        Self { data_dir }
+    pub fn new(data_dir: PathBuf, instance: Option<&str>) -> Self {
+        let file_name = match instance {
+            Some(instance) => format!("app-{instance}"),
+            None => "app".into(),
+        };
+        Self {
+            data_dir,
+            file_name,
+            backup_done: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn get_path(&self, extension: &str) -> PathBuf {
+        self.data_dir
+            .join(format!("{}.{extension}", self.file_name))
     }
 
     fn get_storage_path(&self) -> PathBuf {
-        self.data_dir.join("app.data")
+        self.get_path("data")
+    }
+
+    fn get_backup_path(&self) -> PathBuf {
+        self.get_path("data.bak")
+    }
+
+    /// Temporary file of current process, e.g. `app.data.1234.tmp`.
+    /// Processes saving the same data at the same time don't write to the same file.
+    fn get_temp_path(&self, extension: &str) -> PathBuf {
+        self.get_path(&format!("{extension}.{}.tmp", std::process::id()))
+    }
+
+    fn get_lock_path(&self) -> PathBuf {
+        self.get_path("lock")
     }
 
+    /// Locks data by an exclusive OS lock of a lock file, which contains current process id.
+    /// The OS releases the lock as soon as the process ends (even if it crashes).
+    /// Returns a warning if another process uses the same data already.
+    pub fn lock(&self) -> Result<(StorageLock, Option<String>)> {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(self.get_lock_path())?;
+        match file.try_lock() {
+            Err(TryLockError::WouldBlock) => {
+                let warning = self.locked_by().map(|holder| {
+                    format!(
+                        "Same data is used by {holder} already. Changes of the process quit at last will be kept. Use `--instance` to run another instance with its own data."
+                    )
+                });
+                Ok((StorageLock { _file: None }, warning))
+            }
+            // A file system without support of locks is treated as unlocked.
+            Ok(()) | Err(TryLockError::Error(_)) => {
+                file.set_len(0)?;
+                write!(file, "{}", std::process::id())?;
+                Ok((StorageLock { _file: Some(file) }, None))
+            }
+        }
+    }
+
+    /// Returns another process holding the lock (see `lock`), e.g. `process 1234`
+    pub fn locked_by(&self) -> Option<String> {
+        let file = fs::File::open(self.get_lock_path()).ok()?;
+        // lock can be taken, it's released by dropping `file`
+        if !matches!(file.try_lock(), Err(TryLockError::WouldBlock)) {
+            return None;
+        }
+        // Note: A locked file can't be read on Windows.
+        let pid = fs::read_to_string(self.get_lock_path())
+            .ok()
+            .and_then(|pid| pid.trim().parse::<u32>().ok());
+        match pid {
+            Some(pid) if pid == std::process::id() => None,
+            Some(pid) => Some(format!("process {pid}")),
+            None => Some("another process".to_owned()),
+        }
+    }
+
+    /// Saves data atomically by writing a temporary file first, which replaces `app.data` afterwards.
+    /// `app.data` of a previous session is kept as `app.data.bak` (see `backup`).
     pub fn save(&self, data: AppStorage) -> Result<()> {
-        let file = fs::File::create(self.get_storage_path())?;
-        serde_json::to_writer(file, &data)?;
+        let temp_path = self.get_temp_path("data");
+        let mut file = fs::File::create(&temp_path)?;
+        serde_json::to_writer(&mut file, &data)?;
+        file.sync_all()?;
+
+        if !self.backup_done.load(Ordering::Relaxed) {
+            self.backup()?;
+            self.backup_done.store(true, Ordering::Relaxed);
+        }
+        fs::rename(temp_path, self.get_storage_path())?;
         Ok(())
     }
 
-    pub fn load(&self) -> Result<AppStorage> {
-        let file = fs::File::open(self.get_storage_path())?;
-        let data = serde_json::from_reader(file)?;
-        Ok(data)
+    /// Keeps current `app.data` as `app.data.bak`.
+    /// `app.data` stays in place all the time: It's linked (or copied) to a temporary file,
+    /// which replaces `app.data.bak` atomically.
+    fn backup(&self) -> Result<()> {
+        let path = self.get_storage_path();
+        if !path.exists() {
+            return Ok(());
+        }
+        let temp_path = self.get_temp_path("bak");
+        let _ = fs::remove_file(&temp_path);
+        // fallback for file systems without hard links
+        if fs::hard_link(&path, &temp_path).is_err() {
+            fs::copy(&path, &temp_path)?;
+        }
+        fs::rename(temp_path, self.get_backup_path())?;
+        Ok(())
+    }
+
+    /// Adds a panic hook to save latest known `AppStorage` (see `set_latest`).
+    /// It's a best-effort, all errors are ignored.
+    pub fn set_panic_hook(&self) {
+        let storage = self.clone();
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            if let Some(data) = take_latest() {
+                let _ = storage.save(data);
+            }
+            hook(panic_info);
+        }));
+    }
+
+    /// Loads data of any previous `version` (see `from_value`)
+    fn load_from(path: &Path) -> Result<AppStorage> {
+        let file = fs::File::open(path)?;
+        let value = serde_json::from_reader(file)?;
+        from_value(value)
+    }
+
+    /// Loads `app.data` or recovers data from `app.data.bak` if it fails.
+    /// Returns default data if nothing has been stored before.
+    /// Any recovery is described by a notice to inform users.
+    pub fn load(&self) -> (AppStorage, Option<String>) {
+        let path = self.get_storage_path();
+        let backup_path = self.get_backup_path();
+        let err = match Self::load_from(&path) {
+            Ok(data) => return (data, None),
+            // first start
+            Err(_) if !path.exists() && !backup_path.exists() => {
+                return (AppStorage::default(), None);
+            }
+            Err(err) => err,
+        };
+        error!("Failed to load {:?}: {err}", path);
+
+        match Self::load_from(&backup_path) {
+            Ok(data) => (
+                data,
+                Some(format!(
+                    "Failed to load stored data ({err}). Data has been recovered from backup."
+                )),
+            ),
+            Err(backup_err) => {
+                error!("Failed to load {:?}: {backup_err}", backup_path);
+                (
+                    AppStorage::default(),
+                    Some(format!(
+                        "Failed to load stored data ({err}) and its backup ({backup_err}). Default settings are used."
+                    )),
+                )
+            }
+        }
+    }
+}
+
+/// Lock of a `Storage` (see `Storage::lock`).
+/// It's released by dropping, the lock file itself is kept.
+pub struct StorageLock {
+    /// `None` if the lock is held by another process
+    _file: Option<fs::File>,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use tempfile::TempDir;
+
+    /// `Storage` of a temporary directory, which is removed by dropping `TempDir`
+    fn test_storage() -> (TempDir, Storage) {
+        let dir = TempDir::new().unwrap();
+        let storage = Storage::new(dir.path().to_path_buf(), None);
+        (dir, storage)
+    }
+
+    #[test]
+    fn test_load_first_start() {
+        let (_dir, storage) = test_storage();
+        let (data, notice) = storage.load();
+        assert_eq!(data.content, AppStorage::default().content);
+        assert!(notice.is_none());
+    }
+
+    #[test]
+    fn test_save_keeps_backup() {
+        let (_dir, storage) = test_storage();
+        storage
+            .save(AppStorage {
+                content: Content::Timer,
+                ..AppStorage::default()
+            })
+            .unwrap();
+        // next session
+        let storage = Storage::new(storage.data_dir.clone(), None);
+        for content in [Content::Event, Content::Pomodoro] {
+            storage
+                .save(AppStorage {
+                    content,
+                    ..AppStorage::default()
+                })
+                .unwrap();
+        }
+        assert!(!storage.get_temp_path("data").exists());
+        assert!(!storage.get_temp_path("bak").exists());
+        // backup of previous session is kept once per session only
+        let backup = Storage::load_from(&storage.get_backup_path()).unwrap();
+        assert_eq!(backup.content, Content::Timer);
+        let (data, notice) = storage.load();
+        assert_eq!(data.content, Content::Pomodoro);
+        assert!(notice.is_none());
+    }
+
+    #[test]
+    fn test_load_recovers_backup() {
+        let (_dir, storage) = test_storage();
+        storage
+            .save(AppStorage {
+                content: Content::Timer,
+                ..AppStorage::default()
+            })
+            .unwrap();
+        // next session
+        let storage = Storage::new(storage.data_dir.clone(), None);
+        storage.save(AppStorage::default()).unwrap();
+        // corrupt data
+        fs::write(storage.get_storage_path(), "{\"content\":").unwrap();
+        let (data, notice) = storage.load();
+        assert_eq!(data.content, Content::Timer);
+        assert!(notice.is_some());
+    }
+
+    #[test]
+    fn test_load_fails_without_backup() {
+        let (_dir, storage) = test_storage();
+        fs::write(storage.get_storage_path(), "invalid").unwrap();
+        let (data, notice) = storage.load();
+        assert_eq!(data.content, AppStorage::default().content);
+        assert!(notice.is_some());
+    }
+
+    #[test]
+    fn test_instance() {
+        let (_dir, storage) = test_storage();
+        let instance = Storage::new(storage.data_dir.clone(), Some("work"));
+        instance
+            .save(AppStorage {
+                content: Content::Timer,
+                ..AppStorage::default()
+            })
+            .unwrap();
+        assert!(instance.get_storage_path().ends_with("app-work.data"));
+        // data of default instance is not touched
+        assert!(!storage.get_storage_path().exists());
+        let (data, _) = instance.load();
+        assert_eq!(data.content, Content::Timer);
+    }
+
+    #[test]
+    fn test_parse_instance() {
+        assert_eq!(parse_instance("pomo_1-a"), Ok("pomo_1-a".into()));
+        assert!(parse_instance("").is_err());
+        assert!(parse_instance("../app").is_err());
+        assert!(parse_instance("a b").is_err());
+    }
+
+    #[test]
+    fn test_lock() {
+        let (_dir, storage) = test_storage();
+        let (lock, warning) = storage.lock().unwrap();
+        assert!(warning.is_none());
+        assert_eq!(
+            fs::read_to_string(storage.get_lock_path()).unwrap(),
+            std::process::id().to_string()
+        );
+        assert!(storage.locked_by().is_none());
+        drop(lock);
+        // stale lock file of a process which doesn't run anymore
+        fs::write(storage.get_lock_path(), u32::MAX.to_string()).unwrap();
+        let (_lock, warning) = storage.lock().unwrap();
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn test_lock_warning() {
+        let (_dir, storage) = test_storage();
+        // lock held by another process
+        fs::write(storage.get_lock_path(), "1").unwrap();
+        let other = fs::File::open(storage.get_lock_path()).unwrap();
+        other.lock().unwrap();
+        assert!(storage.locked_by().is_some());
+        let (_lock, warning) = storage.lock().unwrap();
+        assert!(warning.is_some());
+        // released
+        drop(other);
+        assert!(storage.locked_by().is_none());
     }
 }
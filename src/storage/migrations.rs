@@ -0,0 +1,160 @@
+use super::{AppStorage, VERSION};
+use color_eyre::eyre::{Result, eyre};
+use serde_json::{Map, Value};
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// Chain of migrations. Each upgrades data by one version:
+/// `MIGRATIONS[0]` upgrades version `0` to `1`, `MIGRATIONS[1]` upgrades `1` to `2` etc.
+const MIGRATIONS: [Migration; VERSION as usize] = [migrate_v0, migrate_v1, migrate_v2, migrate_v3];
+
+/// Upgrades data of any previous version step by step into current `VERSION`
+pub fn migrate(mut value: Value) -> Result<Value> {
+    let data = value
+        .as_object_mut()
+        .ok_or_else(|| eyre!("Invalid data: object expected"))?;
+
+    let version = match data.get("version") {
+        // data stored before versioning
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| eyre!("Invalid version: {v}"))?,
+    };
+
+    if version > VERSION {
+        return Err(eyre!(
+            "Unsupported version {version} (supported up to {VERSION})"
+        ));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(data);
+        data.insert("version".into(), (from + 1).into());
+    }
+
+    // Data stored before versioning might miss some fields (depending on its release).
+    // All of them are added by using default values of latest `AppStorage` after running all migrations.
+    if version == 0 {
+        let Value::Object(defaults) = serde_json::to_value(AppStorage::default())? else {
+            return Err(eyre!("Invalid default data"));
+        };
+        for (key, value) in defaults {
+            data.entry(key).or_insert(value);
+        }
+    }
+
+    Ok(value)
+}
+
+/// Upgrades data stored before versioning (up to `v1.9.0`)
+fn migrate_v0(data: &mut Map<String, Value>) {
+    // fix typos
+    for name in ["work", "pause", "countdown"] {
+        if let Some(value) = data.remove(&format!("inital_value_{name}")) {
+            data.insert(format!("initial_value_{name}"), value);
+        }
+    }
+    // `Hidden` is deprecated - remove it to use default value instead
+    if data.get("app_time_format").and_then(Value::as_str) == Some("Hidden") {
+        data.remove("app_time_format");
+    }
+}
+
+/// Adds presets (`v1` -> `v2`)
+fn migrate_v1(data: &mut Map<String, Value>) {
+    data.insert(
+        "presets".into(),
+        serde_json::json!({ "presets": [], "recent": [] }),
+    );
+}
+
+/// Adds scheduled start (`v2` -> `v3`)
+fn migrate_v2(data: &mut Map<String, Value>) {
+    data.insert("scheduled_start".into(), Value::Null);
+}
+
+/// Adds tone to play without any sound file (`v3` -> `v4`)
+fn migrate_v3(data: &mut Map<String, Value>) {
+    data.insert("sound_tone".into(), "Beep".into());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::common::{AppTimeFormat, Content, Style, Toggle, Tone};
+    use crate::preset::Presets;
+    use std::time::Duration;
+
+    fn load(fixture: &str) -> Result<AppStorage> {
+        let value = serde_json::from_str(fixture)?;
+        let data = serde_json::from_value(migrate(value)?)?;
+        Ok(data)
+    }
+
+    #[test]
+    fn test_release_v1_7_1() {
+        let data = load(include_str!("fixtures/release-v1.7.1.data")).unwrap();
+        assert_eq!(data.version, VERSION);
+        assert_eq!(data.content, Content::Countdown);
+        // missing before `v1.8.0`
+        assert_eq!(data.vim, Toggle::Off);
+        // `Hidden`
+        assert_eq!(data.app_time_format, AppTimeFormat::default());
+        assert_eq!(data.initial_value_work, Duration::from_secs(2700));
+        assert_eq!(data.current_value_work, Duration::from_millis(2_697_500));
+        assert_eq!(data.initial_value_countdown, Duration::from_secs(1200));
+    }
+
+    #[test]
+    fn test_release_v1_9_0() {
+        let data = load(include_str!("fixtures/release-v1.9.0.data")).unwrap();
+        assert_eq!(data.version, VERSION);
+        assert_eq!(data.content, Content::Pomodoro);
+        assert_eq!(data.vim, Toggle::On);
+        assert_eq!(data.blink, Toggle::On);
+        assert!(matches!(data.style, Style::Light));
+        assert!(data.with_decis);
+        assert_eq!(data.app_time_format, AppTimeFormat::HhMmSs);
+        assert_eq!(data.initial_value_work, Duration::from_secs(2700));
+        assert_eq!(data.current_value_work, Duration::from_millis(2_697_500));
+        assert_eq!(data.initial_value_pause, Duration::from_secs(600));
+        assert_eq!(data.initial_value_countdown, Duration::from_secs(1200));
+        assert_eq!(data.event.title.as_deref(), Some("Christmas"));
+        // added after `v1.9.0`
+        assert_eq!(data.volume, AppStorage::default().volume);
+    }
+
+    #[test]
+    fn test_version_1() {
+        let data = load(include_str!("fixtures/version-1.data")).unwrap();
+        assert_eq!(data.version, VERSION);
+        assert_eq!(data.content, Content::Timer);
+        assert_eq!(data.initial_value_work, Duration::from_secs(2700));
+        assert_eq!(data.current_value_timer, Duration::from_millis(2_700));
+        // added by `v2`
+        assert_eq!(data.presets, Presets::default());
+        // added by `v3`
+        assert_eq!(data.scheduled_start, None);
+        // added by `v4`
+        assert_eq!(data.sound_tone, Tone::Beep);
+    }
+
+    #[test]
+    fn test_current_version() {
+        let value = serde_json::to_value(AppStorage::default()).unwrap();
+        assert_eq!(migrate(value.clone()).unwrap(), value);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let value = serde_json::json!({ "version": VERSION + 1 });
+        assert!(migrate(value).is_err());
+        let value = serde_json::json!({ "version": "1" });
+        assert!(migrate(value).is_err());
+        let value = serde_json::json!([]);
+        assert!(migrate(value).is_err());
+    }
+}
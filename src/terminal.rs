@@ -1,9 +1,12 @@
-use std::io;
+use std::{
+    fmt, io,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 use color_eyre::eyre::Result;
 use crossterm::{
-    cursor, execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    Command, cursor, execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle},
 };
 use ratatui::{Terminal as RatatuiTerminal, backend::CrosstermBackend};
 
@@ -21,6 +24,14 @@ pub fn setup() -> Result<Terminal> {
 }
 
 pub fn teardown() -> Result<()> {
+    // remove progress, but only if it has been reported before
+    if PROGRESS_REPORTED.load(Ordering::Relaxed) {
+        execute!(io::stdout(), Progress::None)?;
+    }
+    // restore title, but only if it has been pushed before
+    if TITLE_PUSHED.load(Ordering::Relaxed) {
+        execute!(io::stdout(), PopTitle)?;
+    }
     execute!(io::stdout(), LeaveAlternateScreen, cursor::Show)?;
     crossterm::terminal::disable_raw_mode()?;
     Ok(())
@@ -35,3 +46,98 @@ fn set_panic_hook() {
         hook(panic_info);
     }));
 }
+
+/// Sets the title of the terminal window (or tab) by using `OSC 0`.
+/// Previous title is pushed once to restore it in `teardown`.
+pub fn set_title(title: &str) -> Result<()> {
+    if !TITLE_PUSHED.swap(true, Ordering::Relaxed) {
+        execute!(io::stdout(), PushTitle)?;
+    }
+    execute!(io::stdout(), SetTitle(title))?;
+    Ok(())
+}
+
+/// Flag to know whether the title has been pushed, which needs to be popped in `teardown`.
+static TITLE_PUSHED: AtomicBool = AtomicBool::new(false);
+
+/// Reports progress to the terminal (e.g. shown in taskbar or tab) by using `OSC 9;4`
+pub fn set_progress(progress: Progress) -> Result<()> {
+    execute!(io::stdout(), progress)?;
+    PROGRESS_REPORTED.store(progress != Progress::None, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Flag to know whether any progress has been reported, which needs to be removed in `teardown`.
+static PROGRESS_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Progress reported by `OSC 9;4`
+/// see https://conemu.github.io/en/AnsiEscapeCodes.html#ConEmu_specific_OSC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// Removes progress
+    None,
+    /// Progress in percentage (0-100)
+    Normal(u16),
+    /// Paused progress in percentage (0-100)
+    Paused(u16),
+}
+
+impl Command for Progress {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            Progress::None => write!(f, "\x1B]9;4;0\x07"),
+            Progress::Normal(p) => write!(f, "\x1B]9;4;1;{}\x07", p.min(&100)),
+            Progress::Paused(p) => write!(f, "\x1B]9;4;4;{}\x07", p.min(&100)),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        // not supported by WinAPI, ANSI only
+        Ok(())
+    }
+}
+
+/// Saves current window title on terminal's stack (`XTWINOPS 22`)
+/// to restore it later by `PopTitle`
+struct PushTitle;
+
+impl Command for PushTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1B[22;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Restores window title saved by `PushTitle` (`XTWINOPS 23`)
+struct PopTitle;
+
+impl Command for PopTitle {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1B[23;0t")
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::to_ansi;
+
+    #[test]
+    fn test_progress_ansi() {
+        assert_eq!(to_ansi(Progress::None), "\x1B]9;4;0\x07");
+        assert_eq!(to_ansi(Progress::Normal(42)), "\x1B]9;4;1;42\x07");
+        assert_eq!(to_ansi(Progress::Paused(42)), "\x1B]9;4;4;42\x07");
+        // clamped to 100
+        assert_eq!(to_ansi(Progress::Normal(120)), "\x1B]9;4;1;100\x07");
+    }
+}
@@ -0,0 +1,8 @@
+use crossterm::Command;
+
+/// ANSI sequence written by a `Command`
+pub fn to_ansi(command: impl Command) -> String {
+    let mut s = String::new();
+    command.write_ansi(&mut s).unwrap();
+    s
+}
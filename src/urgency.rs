@@ -0,0 +1,176 @@
+use crate::duration;
+use color_eyre::{
+    Report,
+    eyre::{ensure, eyre},
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Threshold to switch to another `UrgencyLevel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UrgencyThreshold {
+    /// Percentage of time done (0-100)
+    Percentage(u16),
+    /// Remaining time
+    Remaining(Duration),
+}
+
+impl UrgencyThreshold {
+    fn is_reached(&self, percentage_done: u16, remaining: Duration) -> bool {
+        match self {
+            UrgencyThreshold::Percentage(p) => percentage_done >= *p,
+            UrgencyThreshold::Remaining(d) => remaining <= *d,
+        }
+    }
+}
+
+/// Parses an `UrgencyThreshold`
+/// Supports two formats:
+/// (1) percentage of time done, e.g. "80%"
+/// (2) remaining time, e.g. "5:00" (same formats as `duration::parse_long_duration`)
+pub fn parse_urgency_threshold(arg: &str) -> Result<UrgencyThreshold, Report> {
+    let arg = arg.trim();
+    if let Some(value) = arg.strip_suffix('%') {
+        let percentage = value
+            .trim()
+            .parse::<u16>()
+            .map_err(|_| eyre!("Invalid percentage: '{}'", value))?;
+        ensure!(
+            percentage <= 100,
+            "Percentage must not be greater than 100."
+        );
+        Ok(UrgencyThreshold::Percentage(percentage))
+    } else {
+        let remaining = duration::parse_long_duration(arg)?;
+        Ok(UrgencyThreshold::Remaining(remaining))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrgencyLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl UrgencyLevel {
+    pub fn color(&self) -> Color {
+        match self {
+            UrgencyLevel::Low => Color::Green,
+            UrgencyLevel::Medium => Color::Yellow,
+            UrgencyLevel::High => Color::Red,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrgencyThresholds {
+    /// Threshold to switch from `Low` to `Medium`
+    pub warn: UrgencyThreshold,
+    /// Threshold to switch from `Medium` to `High`
+    pub critical: UrgencyThreshold,
+}
+
+impl Default for UrgencyThresholds {
+    fn default() -> Self {
+        Self {
+            warn: UrgencyThreshold::Percentage(50),
+            critical: UrgencyThreshold::Percentage(80),
+        }
+    }
+}
+
+impl UrgencyThresholds {
+    /// Gets `UrgencyLevel` by given percentage of time done and remaining time.
+    /// `critical` wins over `warn` if both thresholds are reached.
+    pub fn get_level(&self, percentage_done: u16, remaining: Duration) -> UrgencyLevel {
+        if self.critical.is_reached(percentage_done, remaining) {
+            UrgencyLevel::High
+        } else if self.warn.is_reached(percentage_done, remaining) {
+            UrgencyLevel::Medium
+        } else {
+            UrgencyLevel::Low
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duration::ONE_MINUTE;
+
+    #[test]
+    fn test_parse_urgency_threshold() {
+        // percentage
+        assert_eq!(
+            parse_urgency_threshold("50%").unwrap(),
+            UrgencyThreshold::Percentage(50)
+        );
+        assert_eq!(
+            parse_urgency_threshold(" 100% ").unwrap(),
+            UrgencyThreshold::Percentage(100)
+        );
+        // remaining time
+        assert_eq!(
+            parse_urgency_threshold("5:00").unwrap(),
+            UrgencyThreshold::Remaining(ONE_MINUTE * 5)
+        );
+        assert_eq!(
+            parse_urgency_threshold("30").unwrap(),
+            UrgencyThreshold::Remaining(Duration::from_secs(30))
+        );
+        // errors
+        assert!(parse_urgency_threshold("101%").is_err()); // percentage > 100
+        assert!(parse_urgency_threshold("-1%").is_err()); // negative percentage
+        assert!(parse_urgency_threshold("abc%").is_err()); // invalid percentage
+        assert!(parse_urgency_threshold("abc").is_err()); // invalid duration
+    }
+
+    #[test]
+    fn test_get_level_by_percentage() {
+        let thresholds = UrgencyThresholds::default();
+        let remaining = ONE_MINUTE;
+        assert_eq!(thresholds.get_level(0, remaining), UrgencyLevel::Low);
+        assert_eq!(thresholds.get_level(49, remaining), UrgencyLevel::Low);
+        assert_eq!(thresholds.get_level(50, remaining), UrgencyLevel::Medium);
+        assert_eq!(thresholds.get_level(79, remaining), UrgencyLevel::Medium);
+        assert_eq!(thresholds.get_level(80, remaining), UrgencyLevel::High);
+        assert_eq!(thresholds.get_level(100, remaining), UrgencyLevel::High);
+    }
+
+    #[test]
+    fn test_get_level_by_remaining_time() {
+        let thresholds = UrgencyThresholds {
+            warn: UrgencyThreshold::Remaining(ONE_MINUTE * 5),
+            critical: UrgencyThreshold::Remaining(ONE_MINUTE),
+        };
+        assert_eq!(thresholds.get_level(0, ONE_MINUTE * 10), UrgencyLevel::Low);
+        assert_eq!(
+            thresholds.get_level(0, ONE_MINUTE * 5),
+            UrgencyLevel::Medium
+        );
+        assert_eq!(
+            thresholds.get_level(0, ONE_MINUTE * 2),
+            UrgencyLevel::Medium
+        );
+        assert_eq!(thresholds.get_level(0, ONE_MINUTE), UrgencyLevel::High);
+        assert_eq!(thresholds.get_level(0, Duration::ZERO), UrgencyLevel::High);
+    }
+
+    #[test]
+    fn test_get_level_mixed() {
+        // warn by percentage, critical by remaining time
+        let thresholds = UrgencyThresholds {
+            warn: UrgencyThreshold::Percentage(50),
+            critical: UrgencyThreshold::Remaining(ONE_MINUTE),
+        };
+        assert_eq!(thresholds.get_level(10, ONE_MINUTE * 9), UrgencyLevel::Low);
+        assert_eq!(
+            thresholds.get_level(60, ONE_MINUTE * 4),
+            UrgencyLevel::Medium
+        );
+        // critical wins even if warn is not reached yet
+        assert_eq!(thresholds.get_level(10, ONE_MINUTE), UrgencyLevel::High);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::duration::MAX_DURATION;
+use color_eyre::{
+    Report,
+    eyre::{ensure, eyre},
+};
+use std::cmp::min;
+use std::fmt;
+use std::time::Duration;
+use time::macros::format_description;
+use time::{
+    OffsetDateTime, PrimitiveDateTime, Time, UtcOffset, format_description::BorrowedFormatItem,
+};
+
+const TIME_FORMATS: [&[BorrowedFormatItem<'_>]; 2] = [
+    format_description!("[hour padding:none]:[minute]:[second]"),
+    format_description!("[hour padding:none]:[minute]"),
+];
+
+const DATE_TIME_FORMATS: [&[BorrowedFormatItem<'_>]; 2] = [
+    format_description!("[year]-[month]-[day] [hour padding:none]:[minute]:[second]"),
+    format_description!("[year]-[month]-[day] [hour padding:none]:[minute]"),
+];
+
+/// Wall-clock time in local time, e.g. to count down to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallTime {
+    /// Time of day. It's the next one (today or tomorrow).
+    Time(Time),
+    DateTime(PrimitiveDateTime),
+}
+
+impl WallTime {
+    /// Duration from `now` to the wall-clock time (max. `MAX_DURATION`).
+    /// A `Time` which has already passed today rolls over to tomorrow.
+    /// A `DateTime` in the past is an error.
+    pub fn duration_from(&self, now: OffsetDateTime) -> Result<Duration, Report> {
+        self.duration_from_with(now, |date_time| UtcOffset::local_offset_at(date_time).ok())
+    }
+
+    /// Same as `duration_from`, but with a custom local offset at a given instant (e.g. for tests).
+    fn duration_from_with(
+        &self,
+        now: OffsetDateTime,
+        offset_at: impl Fn(OffsetDateTime) -> Option<UtcOffset>,
+    ) -> Result<Duration, Report> {
+        // The offset at the target can differ from the one of `now` (e.g. DST changes in between).
+        // If it's unknown, the offset of `now` is used.
+        let assume_local = |date_time: PrimitiveDateTime| {
+            let guess = date_time.assume_offset(now.offset());
+            date_time.assume_offset(offset_at(guess).unwrap_or(now.offset()))
+        };
+        let target = match self {
+            WallTime::Time(t) => {
+                let today = assume_local(now.date().with_time(*t));
+                if today <= now {
+                    let tomorrow = now.date().saturating_add(time::Duration::DAY);
+                    assume_local(tomorrow.with_time(*t))
+                } else {
+                    today
+                }
+            }
+            WallTime::DateTime(date_time) => assume_local(*date_time),
+        };
+        ensure!(target > now, "Time '{}' has already passed.", self);
+        let d = Duration::try_from(target - now).unwrap_or(MAX_DURATION);
+        Ok(min(MAX_DURATION, d))
+    }
+}
+
+impl fmt::Display for WallTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // safe unwraps: formats have no optional parts
+        match self {
+            WallTime::Time(t) => write!(f, "{}", t.format(TIME_FORMATS[0]).unwrap()),
+            WallTime::DateTime(date_time) => {
+                write!(f, "{}", date_time.format(DATE_TIME_FORMATS[0]).unwrap())
+            }
+        }
+    }
+}
+
+/// Parses a `WallTime`.
+/// Formats: `HH:MM`, `HH:MM:SS`, `YYYY-MM-DD HH:MM` or `YYYY-MM-DD HH:MM:SS`
+pub fn parse_wall_time(arg: &str) -> Result<WallTime, Report> {
+    let arg = arg.trim();
+    if let Some(time) = TIME_FORMATS
+        .iter()
+        .find_map(|format| Time::parse(arg, format).ok())
+    {
+        return Ok(WallTime::Time(time));
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| PrimitiveDateTime::parse(arg, format).ok())
+        .map(WallTime::DateTime)
+        .ok_or_else(|| {
+            eyre!(
+                "Invalid time '{}'. Formats: 'HH:MM', 'HH:MM:SS', 'YYYY-MM-DD HH:MM', 'YYYY-MM-DD HH:MM:SS'",
+                arg
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use time::macros::{datetime, offset, time};
+
+    #[test]
+    fn test_parse_wall_time() {
+        assert_eq!(
+            parse_wall_time("17:30").unwrap(),
+            WallTime::Time(time!(17:30))
+        );
+        assert_eq!(
+            parse_wall_time(" 17:30:15 ").unwrap(),
+            WallTime::Time(time!(17:30:15))
+        );
+        assert_eq!(
+            parse_wall_time("2026-10-19 09:00").unwrap(),
+            WallTime::DateTime(datetime!(2026-10-19 09:00))
+        );
+        assert_eq!(
+            parse_wall_time("2026-10-19 09:00:30").unwrap(),
+            WallTime::DateTime(datetime!(2026-10-19 09:00:30))
+        );
+        assert_eq!(
+            parse_wall_time("9:05").unwrap(),
+            WallTime::Time(time!(09:05))
+        );
+        // errors
+        assert!(parse_wall_time("25:00").is_err());
+        assert!(parse_wall_time("9:5").is_err());
+        assert!(parse_wall_time("17").is_err());
+        assert!(parse_wall_time("2026-13-01 09:00").is_err());
+    }
+
+    #[test]
+    fn test_duration_from() {
+        // offset of `now` everywhere
+        let now = datetime!(2026-10-19 16:00:00.5 +2);
+        // today
+        assert_eq!(
+            WallTime::Time(time!(17:30))
+                .duration_from_with(now, |_| None)
+                .unwrap(),
+            Duration::from_millis((90 * 60 - 1) * 1000 + 500)
+        );
+        // tomorrow
+        assert_eq!(
+            WallTime::Time(time!(15:00))
+                .duration_from_with(now, |_| None)
+                .unwrap(),
+            Duration::from_millis((23 * 3600 - 1) * 1000 + 500)
+        );
+        assert_eq!(
+            WallTime::Time(time!(16:00:00.5))
+                .duration_from_with(now, |_| None)
+                .unwrap(),
+            Duration::from_secs(24 * 3600)
+        );
+        // date time
+        assert_eq!(
+            WallTime::DateTime(datetime!(2026-10-20 16:00:00.5))
+                .duration_from_with(now, |_| None)
+                .unwrap(),
+            Duration::from_secs(24 * 3600)
+        );
+        assert!(
+            WallTime::DateTime(datetime!(2026-10-19 09:00))
+                .duration_from_with(now, |_| None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_duration_from_dst() {
+        // DST ends at 2026-10-25 01:00 UTC: +2 -> +1
+        let offset_at = |date_time: OffsetDateTime| {
+            Some(if date_time < datetime!(2026-10-25 01:00 UTC) {
+                offset!(+2)
+            } else {
+                offset!(+1)
+            })
+        };
+        let now = datetime!(2026-10-24 16:00 +2);
+        // tomorrow, one hour more than without DST change
+        assert_eq!(
+            WallTime::Time(time!(15:00))
+                .duration_from_with(now, offset_at)
+                .unwrap(),
+            Duration::from_secs(24 * 3600)
+        );
+        // date time
+        assert_eq!(
+            WallTime::DateTime(datetime!(2026-10-25 16:00))
+                .duration_from_with(now, offset_at)
+                .unwrap(),
+            Duration::from_secs(25 * 3600)
+        );
+        // unknown offset: offset of `now`
+        assert_eq!(
+            WallTime::DateTime(datetime!(2026-10-25 16:00))
+                .duration_from_with(now, |_| None)
+                .unwrap(),
+            Duration::from_secs(24 * 3600)
+        );
+    }
+}
@@ -1,9 +1,11 @@
+pub mod alarm;
 pub mod clock;
 pub mod clock_elements;
 #[cfg(test)]
 pub mod clock_elements_test;
 #[cfg(test)]
 pub mod clock_test;
+pub mod confirm;
 pub mod countdown;
 pub mod edit_time;
 pub mod event;
@@ -11,5 +13,6 @@ pub mod footer;
 pub mod header;
 pub mod local_time;
 pub mod pomodoro;
+pub mod preset_picker;
 pub mod progressbar;
 pub mod timer;
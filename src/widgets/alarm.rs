@@ -0,0 +1,49 @@
+use crate::notification::NotificationKind;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// Banner of an active `Alarm`
+pub struct AlarmBanner<'a> {
+    pub kind: NotificationKind,
+    pub message: &'a str,
+    pub blink: bool,
+}
+
+const HINT: &str = "press any key to stop";
+
+impl Widget for AlarmBanner<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::raw(self.message),
+            Line::raw(""),
+            Line::styled(HINT, Style::new().add_modifier(Modifier::DIM)),
+        ];
+        let width = lines.iter().map(|l| l.width()).max().unwrap_or_default() as u16 + 4; // + borders + padding
+        let area = area.centered(
+            Constraint::Length(width.min(area.width)),
+            Constraint::Length((lines.len() as u16 + 2).min(area.height)), // + borders
+        );
+
+        let style = if self.blink {
+            Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::new().add_modifier(Modifier::BOLD)
+        };
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .centered()
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .title(Line::raw(format!(" {} DONE ", self.kind).to_uppercase()).centered())
+                    .style(style),
+            )
+            .render(area, buf);
+    }
+}
@@ -6,7 +6,7 @@ use strum::Display;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::Span,
     widgets::{StatefulWidget, Widget},
 };
@@ -172,6 +172,14 @@ pub fn count_by_mode(times: u32, mode: &Mode) -> Duration {
     }
 }
 
+/// Values of a `ClockState` to restore it later (e.g. to undo a reset)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClockSnapshot {
+    pub initial_value: DurationEx,
+    pub current_value: DurationEx,
+    pub mode: Mode,
+}
+
 const RANGE_OF_DONE_COUNT: u64 = 4;
 pub const MAX_DONE_COUNT: u64 = RANGE_OF_DONE_COUNT * 5;
 
@@ -432,6 +440,44 @@ impl<T> ClockState<T> {
         self.update_format();
     }
 
+    /// Prepares a clock to start. A finished clock is reset to its initial value.
+    /// Returns `false` if the clock is running or edited.
+    pub fn prepare_start(&mut self) -> bool {
+        match self.mode {
+            Mode::Initial | Mode::Pause => true,
+            Mode::Done => {
+                self.reset();
+                true
+            }
+            Mode::Tick | Mode::Editable(..) => false,
+        }
+    }
+
+    /// Takes a snapshot of all values not changed by an edit in progress.
+    pub fn snapshot(&self) -> ClockSnapshot {
+        match &self.mode {
+            Mode::Editable(_, prev) => ClockSnapshot {
+                initial_value: self.initial_value,
+                current_value: self.prev_value,
+                mode: *prev.clone(),
+            },
+            mode => ClockSnapshot {
+                initial_value: self.initial_value,
+                current_value: self.current_value,
+                mode: mode.clone(),
+            },
+        }
+    }
+
+    /// Restores values of a snapshot.
+    /// Note: A restored `Mode::Done` does not notify or blink again.
+    pub fn restore(&mut self, snapshot: ClockSnapshot) {
+        self.initial_value = snapshot.initial_value;
+        self.mode = snapshot.mode;
+        self.done_count = None;
+        self.set_current_value(snapshot.current_value);
+    }
+
     pub fn is_done(&self) -> bool {
         self.mode == Mode::Done
     }
@@ -507,6 +553,18 @@ impl ClockState<Countdown> {
         instance
     }
 
+    /// Time the countdown has been running
+    pub fn get_run_duration(&self) -> Duration {
+        Duration::from(self.initial_value).saturating_sub(self.current_value.into())
+    }
+
+    /// Runs again after `duration` has passed in the meantime (e.g. while the app was closed).
+    /// `Mode::Done` will be reached by next `tick`.
+    pub fn resume(&mut self, duration: Duration) {
+        self.set_current_value(self.current_value.saturating_sub(duration.into()));
+        self.run();
+    }
+
     pub fn tick(&mut self) {
         if self.mode == Mode::Tick {
             self.current_value = self.current_value.saturating_sub(self.tick_value);
@@ -554,6 +612,37 @@ impl ClockState<Countdown> {
     pub fn edit_jump_down(&mut self) {
         self.edit_current_down(10);
     }
+
+    /// Adds time without entering edit mode.
+    /// A finished countdown (`Mode::Done`) will run again.
+    /// Note: Initial value is updated only if the countdown has not been started yet.
+    pub fn extend(&mut self, duration: Duration) {
+        if self.is_edit_mode() {
+            return;
+        }
+        let value: DurationEx = Duration::from(self.current_value)
+            .saturating_add(duration)
+            .min(MAX_DURATION)
+            .into();
+        match self.mode {
+            Mode::Initial => self.initial_value = value,
+            Mode::Done => {
+                self.mode = Mode::Tick;
+                self.done_count = None;
+            }
+            _ => {}
+        }
+        self.set_current_value(value);
+    }
+
+    /// Restarts a finished countdown (`Mode::Done`) with given duration
+    pub fn snooze(&mut self, duration: Duration) {
+        if self.is_done() {
+            self.set_current_value(duration.min(MAX_DURATION).into());
+            self.mode = Mode::Tick;
+            self.done_count = None;
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -593,6 +682,22 @@ impl ClockState<Timer> {
         instance
     }
 
+    /// Time the timer has been running
+    pub fn get_run_duration(&self) -> Duration {
+        self.current_value.into()
+    }
+
+    /// Runs again after `duration` has passed in the meantime (e.g. while the app was closed).
+    /// `Mode::Done` will be reached by next `tick`.
+    pub fn resume(&mut self, duration: Duration) {
+        let value: DurationEx = Duration::from(self.current_value)
+            .saturating_add(duration)
+            .min(MAX_DURATION)
+            .into();
+        self.set_current_value(value);
+        self.run();
+    }
+
     pub fn tick(&mut self) {
         if self.mode == Mode::Tick {
             self.current_value = self.current_value.saturating_add(self.tick_value);
@@ -638,6 +743,7 @@ where
 {
     style: DigitStyle,
     blink: bool,
+    color: Option<Color>,
     phantom: PhantomData<T>,
 }
 
@@ -649,10 +755,16 @@ where
         Self {
             style,
             blink,
+            color: None,
             phantom: PhantomData,
         }
     }
 
+    pub fn with_color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
     pub fn get_width(&self, format: &Format, with_decis: bool) -> u16 {
         clock_horizontal_lengths(format, with_decis).iter().sum()
     }
@@ -1677,5 +1789,9 @@ where
             widths,
         };
         render_clock(area, buf, render_state);
+
+        if let Some(color) = self.color {
+            buf.set_style(area, Style::default().fg(color));
+        }
     }
 }
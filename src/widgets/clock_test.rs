@@ -1122,3 +1122,177 @@ fn test_edit_down_hhmmss() {
     c.edit_down();
     assert_eq!(Duration::from(*c.get_current_value()), Duration::ZERO);
 }
+
+#[test]
+fn test_extend_initial() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    c.extend(ONE_MINUTE);
+    assert_eq!(
+        Duration::from(*c.get_current_value()),
+        ONE_HOUR + ONE_MINUTE
+    );
+    // initial value follows
+    assert_eq!(
+        Duration::from(*c.get_initial_value()),
+        ONE_HOUR + ONE_MINUTE
+    );
+    assert!(c.is_initial());
+}
+
+#[test]
+fn test_extend_running() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    c.run();
+    c.extend(ONE_MINUTE * 5);
+    assert_eq!(
+        Duration::from(*c.get_current_value()),
+        ONE_HOUR + ONE_MINUTE * 5
+    );
+    // initial value is untouched
+    assert_eq!(Duration::from(*c.get_initial_value()), ONE_HOUR);
+    assert!(c.is_running());
+}
+
+#[test]
+fn test_extend_done() {
+    let mut c = ClockState::<Countdown>::new(ClockStateArgs {
+        initial_value: ONE_HOUR,
+        current_value: Duration::ZERO,
+        tick_value: ONE_DECI_SECOND,
+        with_decis: false,
+        app_tx: None,
+    })
+    .with_mode(Mode::Done);
+    c.extend(ONE_MINUTE);
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_MINUTE);
+    // lifted out of `Done`
+    assert!(c.is_running());
+}
+
+#[test]
+fn test_extend_max() {
+    let mut c = ClockState::<Countdown>::new(ClockStateArgs {
+        initial_value: MAX_DURATION,
+        current_value: MAX_DURATION,
+        tick_value: ONE_DECI_SECOND,
+        with_decis: false,
+        app_tx: None,
+    });
+    c.extend(ONE_MINUTE);
+    assert_eq!(Duration::from(*c.get_current_value()), MAX_DURATION);
+}
+
+#[test]
+fn test_extend_edit_mode() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    c.toggle_edit();
+    c.extend(ONE_MINUTE);
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_HOUR);
+}
+
+#[test]
+fn test_snooze() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    // ignored if not done
+    c.snooze(ONE_MINUTE);
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_HOUR);
+    assert!(c.is_initial());
+
+    let mut c = c.with_mode(Mode::Done);
+    c.snooze(ONE_MINUTE * 5);
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_MINUTE * 5);
+    assert!(c.is_running());
+}
+
+#[test]
+fn test_snapshot_restore() {
+    let mut c = ClockState::<Timer>::new(default_args());
+    c.run();
+    let snapshot = c.snapshot();
+    c.reset();
+    c.set_initial_value(ONE_MINUTE.into());
+    c.restore(snapshot);
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_HOUR);
+    assert_eq!(Duration::from(*c.get_initial_value()), ONE_HOUR);
+    assert!(c.is_running());
+}
+
+#[test]
+fn test_snapshot_edit_mode() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    c.toggle_edit();
+    c.edit_up();
+    // values before editing
+    let snapshot = c.snapshot();
+    assert_eq!(Duration::from(snapshot.current_value), ONE_HOUR);
+    assert_eq!(snapshot.mode, Mode::Initial);
+}
+
+#[test]
+fn test_run_duration() {
+    let c = ClockState::<Countdown>::new(ClockStateArgs {
+        initial_value: ONE_HOUR,
+        current_value: ONE_MINUTE,
+        tick_value: ONE_DECI_SECOND,
+        with_decis: false,
+        app_tx: None,
+    });
+    assert_eq!(c.get_run_duration(), ONE_HOUR - ONE_MINUTE);
+    let c = ClockState::<Timer>::new(ClockStateArgs {
+        initial_value: Duration::ZERO,
+        current_value: ONE_MINUTE,
+        tick_value: ONE_DECI_SECOND,
+        with_decis: false,
+        app_tx: None,
+    });
+    assert_eq!(c.get_run_duration(), ONE_MINUTE);
+}
+
+#[test]
+fn test_resume_countdown() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    c.resume(ONE_MINUTE);
+    assert_eq!(
+        Duration::from(*c.get_current_value()),
+        ONE_HOUR - ONE_MINUTE
+    );
+    assert!(c.is_running());
+    // finished in the meantime
+    c.resume(ONE_HOUR * 2);
+    assert_eq!(Duration::from(*c.get_current_value()), Duration::ZERO);
+    c.tick();
+    assert!(c.is_done());
+}
+
+#[test]
+fn test_resume_timer() {
+    let mut c = ClockState::<Timer>::new(default_args());
+    c.resume(ONE_MINUTE);
+    assert_eq!(
+        Duration::from(*c.get_current_value()),
+        ONE_HOUR + ONE_MINUTE
+    );
+    assert!(c.is_running());
+    c.resume(MAX_DURATION);
+    assert_eq!(Duration::from(*c.get_current_value()), MAX_DURATION);
+}
+
+#[test]
+fn test_prepare_start() {
+    let mut c = ClockState::<Countdown>::new(default_args());
+    assert!(c.prepare_start());
+    c.run();
+    assert!(!c.prepare_start());
+    c.toggle_pause();
+    assert!(c.prepare_start());
+    // finished clock starts from scratch
+    c.resume(ONE_HOUR);
+    c.tick();
+    assert!(c.is_done());
+    assert!(c.prepare_start());
+    assert!(c.is_initial());
+    assert_eq!(Duration::from(*c.get_current_value()), ONE_HOUR);
+    // edited clock
+    c.toggle_edit();
+    assert!(!c.prepare_start());
+}
@@ -0,0 +1,73 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+/// Popup asking to confirm an action
+pub struct ConfirmPopup<'a> {
+    pub title: &'a str,
+    pub message: &'a str,
+    /// Pairs of key and description
+    pub options: &'a [(&'a str, &'a str)],
+}
+
+impl Widget for ConfirmPopup<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut option_spans = vec![];
+        for (i, (key, description)) in self.options.iter().enumerate() {
+            if i > 0 {
+                option_spans.push(Span::raw("  "));
+            }
+            option_spans.push(Span::styled(
+                *key,
+                Style::new().add_modifier(Modifier::BOLD),
+            ));
+            option_spans.push(Span::styled(
+                format!(" {description}"),
+                Style::new().add_modifier(Modifier::ITALIC),
+            ));
+        }
+        let max_width = area.width.saturating_sub(4) as usize; // - borders - padding
+        let mut lines: Vec<Line> = wrap(self.message, max_width)
+            .into_iter()
+            .map(Line::raw)
+            .collect();
+        lines.push(Line::raw(""));
+        lines.push(Line::from(option_spans));
+        let width = lines.iter().map(|l| l.width()).max().unwrap_or_default() as u16 + 4; // + borders + padding
+        let area = area.centered(
+            Constraint::Length(width.min(area.width)),
+            Constraint::Length((lines.len() as u16 + 2).min(area.height)), // + borders
+        );
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .centered()
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .title(Line::raw(format!(" {} ", self.title).to_uppercase()).centered()),
+            )
+            .render(area, buf);
+    }
+}
+
+/// Wraps `text` by words into lines of `max_width`
+fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
@@ -1,10 +1,11 @@
 use crate::{
-    common::{AppTime, AppTimeFormat, Style},
+    common::{AppTime, AppTimeFormat, ExtendSettings, Style},
     constants::TICK_VALUE_MS,
     duration::{DurationEx, MAX_DURATION},
     events::{AppEventTx, TuiEvent, TuiEventHandler},
+    history::History,
     widgets::{
-        clock::{self, ClockState, ClockStateArgs, ClockWidget, Mode as ClockMode},
+        clock::{self, ClockSnapshot, ClockState, ClockStateArgs, ClockWidget, Mode as ClockMode},
         edit_time::{EditTimeState, EditTimeStateArgs, EditTimeWidget},
     },
 };
@@ -13,6 +14,7 @@ use ratatui::{
     buffer::Buffer,
     crossterm::event::KeyCode,
     layout::{Constraint, Layout, Rect},
+    style::Color,
     text::Line,
     widgets::{StatefulWidget, Widget},
 };
@@ -29,6 +31,14 @@ pub struct CountdownStateArgs {
     pub with_decis: bool,
     pub app_tx: AppEventTx,
     pub vim_motions: bool,
+    pub extend_settings: ExtendSettings,
+}
+
+/// Values of both clocks to undo/redo changes
+#[derive(Debug, PartialEq)]
+pub struct CountdownSnapshot {
+    clock: ClockSnapshot,
+    elapsed_clock: ClockSnapshot,
 }
 
 /// State for Countdown Widget
@@ -46,6 +56,10 @@ pub struct CountdownState {
     edit_time: Option<EditTimeState>,
     /// Whether Vim motions are enabled
     vim_motions: bool,
+    /// Durations to extend or snooze the countdown
+    extend_settings: ExtendSettings,
+    /// Snapshots to undo/redo resets and edits
+    history: History<CountdownSnapshot>,
 }
 
 impl CountdownState {
@@ -59,6 +73,7 @@ impl CountdownState {
             target_time_format: app_time_format,
             app_tx,
             vim_motions,
+            extend_settings,
         } = args;
 
         Self {
@@ -90,6 +105,8 @@ impl CountdownState {
             target_time: OffsetDateTime::from(app_time),
             edit_time: None,
             vim_motions,
+            extend_settings,
+            history: History::default(),
         }
     }
 
@@ -149,6 +166,96 @@ impl CountdownState {
         self.edit_time = None;
     }
 
+    /// Time the countdown has been running, incl. time after `DONE`
+    pub fn get_run_duration(&self) -> Duration {
+        self.clock
+            .get_run_duration()
+            .saturating_add((*self.get_elapsed_value()).into())
+    }
+
+    /// Continues running after `duration` has passed in the meantime (e.g. while the app was closed).
+    /// Time exceeding the countdown is counted by `elapsed_clock`.
+    pub fn resume(&mut self, duration: Duration) {
+        if self.clock.is_done() {
+            let elapsed = Duration::from(*self.get_elapsed_value()).saturating_add(duration);
+            self.elapsed_clock.set_current_value(elapsed.into());
+            self.elapsed_clock.run();
+        } else {
+            let overtime = duration.saturating_sub((*self.clock.get_current_value()).into());
+            self.clock.resume(duration);
+            // `elapsed_clock` starts running as soon as `clock` is done
+            self.elapsed_clock.set_current_value(overtime.into());
+        }
+        self.target_time = self.time_to_edit();
+    }
+
+    /// Starts the countdown, which should have been started `duration` ago.
+    /// A finished countdown starts again from its initial value.
+    /// A running or edited countdown is not changed.
+    pub fn start(&mut self, duration: Duration) {
+        if self.is_running() || self.is_time_edit_mode() || !self.clock.prepare_start() {
+            return;
+        }
+        self.elapsed_clock.reset();
+        self.resume(duration);
+    }
+
+    pub fn snapshot(&self) -> CountdownSnapshot {
+        CountdownSnapshot {
+            clock: self.clock.snapshot(),
+            elapsed_clock: self.elapsed_clock.snapshot(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: CountdownSnapshot) {
+        self.clock.restore(snapshot.clock);
+        self.elapsed_clock.restore(snapshot.elapsed_clock);
+        self.target_time = self.time_to_edit();
+    }
+
+    /// Stores current values to undo next change
+    fn save_snapshot(&mut self) {
+        self.history.push(self.snapshot());
+    }
+
+    /// Stores values taken `before` a change to undo it, but only if something has changed
+    fn save_changed_snapshot(&mut self, before: CountdownSnapshot) {
+        if before != self.snapshot() {
+            self.history.push(before);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    /// Adds `duration` to a running, paused or finished countdown
+    fn extend(&mut self, duration: Duration) {
+        let before = self.snapshot();
+        self.clock.extend(duration);
+        self.target_time = self.time_to_edit();
+        // time after `DONE` does not count anymore
+        self.elapsed_clock.reset();
+        self.save_changed_snapshot(before);
+    }
+
+    /// Sets a new initial value (e.g. of a preset) and resets both clocks
+    pub fn apply_preset(&mut self, value: Duration) {
+        self.save_snapshot();
+        self.clock.set_initial_value(value.into());
+        self.clock.reset();
+        self.elapsed_clock.reset();
+        self.target_time = self.time_to_edit();
+    }
+
     pub fn is_clock_edit_mode(&self) -> bool {
         self.clock.is_edit_mode()
     }
@@ -191,6 +298,7 @@ impl TuiEventHandler for CountdownState {
                     }
                     // Apply changes and set new initial value
                     KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        let before = self.snapshot();
                         // toggle edit mode
                         self.clock.toggle_edit();
                         // set initial value
@@ -198,13 +306,16 @@ impl TuiEventHandler for CountdownState {
                             .set_initial_value(*self.clock.get_current_value());
                         // always reset `elapsed_clock`
                         self.elapsed_clock.reset();
+                        self.save_changed_snapshot(before);
                     }
                     // Apply changes
                     KeyCode::Char('s') => {
+                        let before = self.snapshot();
                         // toggle edit mode
                         self.clock.toggle_edit();
                         // always reset `elapsed_clock`
                         self.elapsed_clock.reset();
+                        self.save_changed_snapshot(before);
                     }
                     KeyCode::Right if !self.vim_motions => {
                         self.clock.edit_prev();
@@ -258,6 +369,7 @@ impl TuiEventHandler for CountdownState {
                     }
                     // Apply changes and set new initial value
                     KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        let before = self.snapshot();
                         if let Some(edit_time) = &mut self.edit_time.clone() {
                             // Order matters:
                             // 1. update current value
@@ -268,14 +380,17 @@ impl TuiEventHandler for CountdownState {
                         }
                         // always reset `elapsed_clock`
                         self.elapsed_clock.reset();
+                        self.save_changed_snapshot(before);
                     }
                     // Apply changes of editing by local time
                     KeyCode::Char('s') => {
+                        let before = self.snapshot();
                         if let Some(edit_time) = &mut self.edit_time.clone() {
                             self.edit_time_done(edit_time)
                         }
                         // always reset `elapsed_clock`
                         self.elapsed_clock.reset();
+                        self.save_changed_snapshot(before);
                     }
                     // move edit position to the left
                     KeyCode::Left if !self.vim_motions => {
@@ -339,6 +454,7 @@ impl TuiEventHandler for CountdownState {
             // default mode
             TuiEvent::Crossterm(CrosstermEvent::Key(key)) => match key.code {
                 KeyCode::Char('r') => {
+                    self.save_snapshot();
                     // reset both clocks to use intial values
                     self.clock.reset();
                     self.elapsed_clock.reset();
@@ -362,6 +478,26 @@ impl TuiEventHandler for CountdownState {
                         self.edit_time_done(edit_time);
                     }
                 }
+                // extend countdown
+                KeyCode::Char('+') => {
+                    self.extend(self.extend_settings.extend);
+                }
+                KeyCode::Char('*') => {
+                    self.extend(self.extend_settings.extend_more);
+                }
+                // snooze finished countdown
+                KeyCode::Char('n') if self.clock.is_done() => {
+                    self.save_snapshot();
+                    self.clock.snooze(self.extend_settings.snooze);
+                    self.target_time = self.time_to_edit();
+                    self.elapsed_clock.reset();
+                }
+                KeyCode::Char('u') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.undo();
+                }
+                KeyCode::Char('U') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.redo();
+                }
                 // Enter edit by local time mode
                 KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     // set `edit_time`
@@ -397,6 +533,7 @@ impl TuiEventHandler for CountdownState {
 pub struct Countdown {
     pub style: Style,
     pub blink: bool,
+    pub urgency_color: Option<Color>,
 }
 
 fn human_days_diff(a: &OffsetDateTime, b: &OffsetDateTime) -> String {
@@ -458,7 +595,7 @@ impl StatefulWidget for Countdown {
                 }
                 .to_uppercase(),
             );
-            let widget = ClockWidget::new(self.style, self.blink);
+            let widget = ClockWidget::new(self.style, self.blink).with_color(self.urgency_color);
             let label_target_time = Line::raw(
                 if let Some(tf) = state.target_time_format
                     // hide target time if we edit by time - no duplication of information then
@@ -495,3 +632,98 @@ impl StatefulWidget for Countdown {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::duration::{ONE_HOUR, ONE_MINUTE};
+    use tokio::sync::mpsc;
+
+    fn countdown(current_value: Duration) -> CountdownState {
+        CountdownState::new(CountdownStateArgs {
+            initial_value: ONE_HOUR,
+            current_value,
+            elapsed_value: Duration::ZERO,
+            app_time: AppTime::new(),
+            target_time_format: None,
+            with_decis: false,
+            app_tx: mpsc::unbounded_channel().0,
+            vim_motions: false,
+            extend_settings: ExtendSettings::default(),
+        })
+    }
+
+    #[test]
+    fn test_start() {
+        let mut c = countdown(ONE_HOUR);
+        c.start(ONE_MINUTE);
+        assert!(c.get_clock().is_running());
+        assert_eq!(
+            Duration::from(*c.get_clock().get_current_value()),
+            ONE_HOUR - ONE_MINUTE
+        );
+    }
+
+    #[test]
+    fn test_start_done() {
+        let mut c = countdown(Duration::ZERO);
+        assert!(c.get_clock().is_done());
+        c.start(Duration::ZERO);
+        // starts again from its initial value
+        assert!(c.get_clock().is_running());
+        assert!(!c.get_clock().is_done());
+        assert_eq!(Duration::from(*c.get_clock().get_current_value()), ONE_HOUR);
+        assert_eq!(Duration::from(*c.get_elapsed_value()), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_undo_key() {
+        let key = |code, modifiers| {
+            TuiEvent::Crossterm(CrosstermEvent::Key(crossterm::event::KeyEvent::new(
+                code, modifiers,
+            )))
+        };
+        let mut c = countdown(ONE_MINUTE);
+        c.update(key(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert_eq!(Duration::from(*c.get_clock().get_current_value()), ONE_HOUR);
+        // `ctrl+u` (e.g. to unlock kiosk mode) is not an undo
+        c.update(key(KeyCode::Char('u'), KeyModifiers::CONTROL));
+        assert_eq!(Duration::from(*c.get_clock().get_current_value()), ONE_HOUR);
+        c.update(key(KeyCode::Char('u'), KeyModifiers::NONE));
+        assert_eq!(
+            Duration::from(*c.get_clock().get_current_value()),
+            ONE_MINUTE
+        );
+    }
+
+    #[test]
+    fn test_undo_unchanged_apply() {
+        let key = |code| {
+            TuiEvent::Crossterm(CrosstermEvent::Key(crossterm::event::KeyEvent::new(
+                code,
+                KeyModifiers::NONE,
+            )))
+        };
+        let mut c = countdown(ONE_MINUTE);
+        c.update(key(KeyCode::Char('r')));
+        // apply an edit without any change
+        c.update(key(KeyCode::Char('e')));
+        c.update(key(KeyCode::Char('s')));
+        // undo of `r`
+        c.update(key(KeyCode::Char('u')));
+        assert_eq!(
+            Duration::from(*c.get_clock().get_current_value()),
+            ONE_MINUTE
+        );
+    }
+
+    #[test]
+    fn test_start_edit() {
+        let mut c = countdown(ONE_HOUR);
+        c.clock.toggle_edit();
+        c.start(Duration::ZERO);
+        assert!(c.is_clock_edit_mode());
+        assert!(!c.is_running());
+    }
+}
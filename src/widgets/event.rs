@@ -141,7 +141,7 @@ impl EventState {
         get_percentage(self.start_time, self.event_time, self.app_time)
     }
 
-    pub fn get_duration(&mut self) -> CalendarDuration {
+    pub fn get_duration(&self) -> CalendarDuration {
         CalendarDuration::from_start_end_times(self.event_time, self.app_time)
     }
 
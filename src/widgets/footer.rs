@@ -187,6 +187,10 @@ impl StatefulWidget for Footer {
                             ),
                             ITALIC,
                         ),
+                        Span::from(WIDE_SPACE),
+                        Span::styled("z", BOLD),
+                        Span::from(SPACE),
+                        Span::styled("zen mode", ITALIC),
                     ])),
                 ]),
             ];
@@ -227,6 +231,10 @@ impl StatefulWidget for Footer {
                                         Span::styled("r", BOLD),
                                         Span::from(SPACE),
                                         Span::styled("reset clock", ITALIC),
+                                        Span::from(WIDE_SPACE),
+                                        Span::styled("u U", BOLD),
+                                        Span::from(SPACE),
+                                        Span::styled("undo/redo", ITALIC),
                                     ]);
                                     if self.selected_content == Content::Pomodoro {
                                         spans.extend_from_slice(&[
@@ -236,6 +244,24 @@ impl StatefulWidget for Footer {
                                             Span::styled("reset clocks/rounds", ITALIC),
                                         ]);
                                     }
+                                    if self.selected_content == Content::Countdown
+                                        || self.selected_content == Content::Pomodoro
+                                    {
+                                        spans.extend_from_slice(&[
+                                            Span::from(WIDE_SPACE),
+                                            Span::styled("+ *", BOLD),
+                                            Span::from(SPACE),
+                                            Span::styled("extend", ITALIC),
+                                            Span::from(WIDE_SPACE),
+                                            Span::styled("n", BOLD),
+                                            Span::from(SPACE),
+                                            Span::styled("snooze", ITALIC),
+                                            Span::from(WIDE_SPACE),
+                                            Span::styled("p", BOLD),
+                                            Span::from(SPACE),
+                                            Span::styled("presets", ITALIC),
+                                        ]);
+                                    }
                                     spans
                                 }
                                 AppEditMode::None if self.selected_content == Content::Event => {
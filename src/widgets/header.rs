@@ -1,6 +1,8 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::Color,
+    text::Line,
     widgets::{Block, Borders, Widget},
 };
 
@@ -9,14 +11,22 @@ use crate::widgets::progressbar::Progressbar;
 #[derive(Debug, Clone)]
 pub struct Header {
     pub percentage: Option<u16>,
+    pub color: Option<Color>,
+    /// Label to show on top of the header, e.g. about an armed clock
+    pub label: Option<String>,
 }
 
 impl Widget for Header {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if let Some(percentage) = self.percentage {
-            Progressbar::new(percentage).render(area, buf);
+            Progressbar::new(percentage)
+                .with_color(self.color)
+                .render(area, buf);
         } else {
             Block::new().borders(Borders::TOP).render(area, buf);
         }
+        if let Some(label) = self.label {
+            Line::raw(format!(" {label} ")).centered().render(area, buf);
+        }
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
-    common::Style,
+    common::{ExtendSettings, Style},
     constants::TICK_VALUE_MS,
     events::{AppEventTx, TuiEvent, TuiEventHandler},
-    widgets::clock::{ClockState, ClockStateArgs, ClockWidget, Countdown},
+    history::History,
+    widgets::clock::{ClockSnapshot, ClockState, ClockStateArgs, ClockWidget, Countdown},
 };
 use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyModifiers};
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
+    style::Color,
     text::Line,
     widgets::{StatefulWidget, Widget},
 };
@@ -41,11 +43,22 @@ impl ClockMap {
     }
 }
 
+/// Values of both clocks and round to undo/redo changes
+#[derive(Debug, PartialEq)]
+pub struct PomodoroSnapshot {
+    mode: Mode,
+    work: ClockSnapshot,
+    pause: ClockSnapshot,
+    round: u64,
+}
+
 pub struct PomodoroState {
     mode: Mode,
     clock_map: ClockMap,
     round: u64,
     vim_motions: bool,
+    extend_settings: ExtendSettings,
+    history: History<PomodoroSnapshot>,
 }
 
 pub struct PomodoroStateArgs {
@@ -58,6 +71,7 @@ pub struct PomodoroStateArgs {
     pub app_tx: AppEventTx,
     pub round: u64,
     pub vim_motions: bool,
+    pub extend_settings: ExtendSettings,
 }
 
 impl PomodoroState {
@@ -72,6 +86,7 @@ impl PomodoroState {
             app_tx,
             round,
             vim_motions,
+            extend_settings,
         } = args;
         Self {
             mode,
@@ -95,6 +110,8 @@ impl PomodoroState {
             },
             round,
             vim_motions,
+            extend_settings,
+            history: History::default(),
         }
     }
 
@@ -135,6 +152,72 @@ impl PomodoroState {
         self.clock_map.pause.with_decis = with_decis;
     }
 
+    /// Continues running current clock after `duration` has passed in the meantime (e.g. while the app was closed)
+    pub fn resume(&mut self, duration: Duration) {
+        self.get_clock_mut().resume(duration);
+    }
+
+    /// Starts current clock, which should have been started `duration` ago.
+    /// A finished clock starts again from its initial value.
+    /// A running or edited clock is not changed.
+    pub fn start(&mut self, duration: Duration) {
+        if self.get_clock_mut().prepare_start() {
+            self.resume(duration);
+        }
+    }
+
+    pub fn snapshot(&self) -> PomodoroSnapshot {
+        PomodoroSnapshot {
+            mode: self.mode.clone(),
+            work: self.clock_map.work.snapshot(),
+            pause: self.clock_map.pause.snapshot(),
+            round: self.round,
+        }
+    }
+
+    fn restore(&mut self, snapshot: PomodoroSnapshot) {
+        self.mode = snapshot.mode;
+        self.clock_map.work.restore(snapshot.work);
+        self.clock_map.pause.restore(snapshot.pause);
+        self.round = snapshot.round;
+    }
+
+    /// Stores current values to undo next change
+    fn save_snapshot(&mut self) {
+        self.history.push(self.snapshot());
+    }
+
+    /// Stores values taken `before` a change to undo it, but only if something has changed
+    fn save_changed_snapshot(&mut self, before: PomodoroSnapshot) {
+        if before != self.snapshot() {
+            self.history.push(before);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot);
+        }
+    }
+
+    /// Sets new initial values (e.g. of a preset) and resets both clocks
+    pub fn apply_preset(&mut self, work: Duration, pause: Duration) {
+        self.save_snapshot();
+        for (clock, value) in [
+            (&mut self.clock_map.work, work),
+            (&mut self.clock_map.pause, pause),
+        ] {
+            clock.set_initial_value(value.into());
+            clock.reset();
+        }
+    }
+
     pub fn next(&mut self) {
         self.mode = match self.mode {
             Mode::Pause => Mode::Work,
@@ -163,14 +246,18 @@ impl TuiEventHandler for PomodoroState {
                 }
                 // Apply changes and update initial value
                 KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let before = self.snapshot();
                     self.get_clock_mut().toggle_edit();
                     // update initial value
                     let c = *self.get_clock().get_current_value();
                     self.get_clock_mut().set_initial_value(c);
+                    self.save_changed_snapshot(before);
                 }
                 // Apply changes
                 KeyCode::Char('s') => {
+                    let before = self.snapshot();
                     self.get_clock_mut().toggle_edit();
+                    self.save_changed_snapshot(before);
                 }
                 // change value up
                 KeyCode::Up if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -228,6 +315,31 @@ impl TuiEventHandler for PomodoroState {
                 KeyCode::Char('e') => {
                     self.get_clock_mut().toggle_edit();
                 }
+                KeyCode::Char('u') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.undo();
+                }
+                KeyCode::Char('U') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.redo();
+                }
+                // extend current clock
+                KeyCode::Char('+') => {
+                    let before = self.snapshot();
+                    let d = self.extend_settings.extend;
+                    self.get_clock_mut().extend(d);
+                    self.save_changed_snapshot(before);
+                }
+                KeyCode::Char('*') => {
+                    let before = self.snapshot();
+                    let d = self.extend_settings.extend_more;
+                    self.get_clock_mut().extend(d);
+                    self.save_changed_snapshot(before);
+                }
+                // snooze finished clock
+                KeyCode::Char('n') if self.get_clock().is_done() => {
+                    self.save_snapshot();
+                    let d = self.extend_settings.snooze;
+                    self.get_clock_mut().snooze(d);
+                }
                 // toggle WORK/PAUSE
                 KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) && !self.vim_motions => {
                     // `next` is acting as same as a "prev" function we don't have
@@ -245,12 +357,14 @@ impl TuiEventHandler for PomodoroState {
                 }
                 // reset rounds AND clocks
                 KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.save_snapshot();
                     self.round = 1;
                     self.get_clock_work_mut().reset();
                     self.get_clock_pause_mut().reset();
                 }
                 // reset current clock
                 KeyCode::Char('r') => {
+                    self.save_snapshot();
                     // increase round before (!!) resetting the clock
                     if self.get_mode() == &Mode::Work && self.get_clock().is_done() {
                         self.round += 1;
@@ -268,12 +382,13 @@ impl TuiEventHandler for PomodoroState {
 pub struct PomodoroWidget {
     pub style: Style,
     pub blink: bool,
+    pub urgency_color: Option<Color>,
 }
 
 impl StatefulWidget for PomodoroWidget {
     type State = PomodoroState;
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        let clock_widget = ClockWidget::new(self.style, self.blink);
+        let clock_widget = ClockWidget::new(self.style, self.blink).with_color(self.urgency_color);
         let label = Line::raw(
             (format!(
                 "Pomodoro {} {}",
@@ -312,3 +427,78 @@ impl StatefulWidget for PomodoroWidget {
         label_round.centered().render(v4, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::duration::ONE_MINUTE;
+    use tokio::sync::mpsc;
+
+    fn pomodoro(mode: Mode, current_value_pause: Duration) -> PomodoroState {
+        PomodoroState::new(PomodoroStateArgs {
+            mode,
+            initial_value_work: ONE_MINUTE * 25,
+            current_value_work: ONE_MINUTE * 25,
+            initial_value_pause: ONE_MINUTE * 5,
+            current_value_pause,
+            with_decis: false,
+            app_tx: mpsc::unbounded_channel().0,
+            round: 1,
+            vim_motions: false,
+            extend_settings: ExtendSettings::default(),
+        })
+    }
+
+    #[test]
+    fn test_start() {
+        let mut p = pomodoro(Mode::Work, ONE_MINUTE * 5);
+        p.start(ONE_MINUTE);
+        assert!(p.get_clock_work().is_running());
+        assert!(!p.get_clock_pause().is_running());
+        assert_eq!(
+            Duration::from(*p.get_clock().get_current_value()),
+            ONE_MINUTE * 24
+        );
+        // running clock is not changed
+        p.start(ONE_MINUTE);
+        assert_eq!(
+            Duration::from(*p.get_clock().get_current_value()),
+            ONE_MINUTE * 24
+        );
+    }
+
+    #[test]
+    fn test_start_done() {
+        let mut p = pomodoro(Mode::Pause, Duration::ZERO);
+        assert!(p.get_clock().is_done());
+        p.start(Duration::ZERO);
+        // starts again from its initial value
+        assert!(p.get_clock_pause().is_running());
+        assert_eq!(
+            Duration::from(*p.get_clock().get_current_value()),
+            ONE_MINUTE * 5
+        );
+    }
+
+    #[test]
+    fn test_undo_unchanged_apply() {
+        let key = |code| {
+            TuiEvent::Crossterm(CrosstermEvent::Key(crossterm::event::KeyEvent::new(
+                code,
+                KeyModifiers::NONE,
+            )))
+        };
+        let mut p = pomodoro(Mode::Pause, ONE_MINUTE);
+        p.update(key(KeyCode::Char('r')));
+        // apply an edit without any change
+        p.update(key(KeyCode::Char('e')));
+        p.update(key(KeyCode::Char('s')));
+        // undo of `r`
+        p.update(key(KeyCode::Char('u')));
+        assert_eq!(
+            Duration::from(*p.get_clock().get_current_value()),
+            ONE_MINUTE
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::preset::PresetValue;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, StatefulWidget, Widget},
+};
+
+/// Entry of `PresetPicker`
+#[derive(Debug, Clone)]
+pub struct PresetItem {
+    /// Name of a preset. `None` for a recently used duration.
+    pub name: Option<String>,
+    pub value: PresetValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct PresetPickerState {
+    items: Vec<PresetItem>,
+    selected: usize,
+}
+
+impl PresetPickerState {
+    pub fn new(items: Vec<PresetItem>) -> Self {
+        Self { items, selected: 0 }
+    }
+
+    pub fn next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    pub fn get_selected(&self) -> Option<PresetValue> {
+        self.items.get(self.selected).map(|item| item.value)
+    }
+}
+
+/// Popup to pick a preset or a recently used duration
+pub struct PresetPicker;
+
+impl StatefulWidget for PresetPicker {
+    type State = PresetPickerState;
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let bold = Style::new().add_modifier(Modifier::BOLD);
+        let italic = Style::new().add_modifier(Modifier::ITALIC);
+
+        let mut lines: Vec<Line> = if state.items.is_empty() {
+            vec![Line::raw("No presets. Add them by `--add-preset`.")]
+        } else {
+            let name_width = state
+                .items
+                .iter()
+                .map(|item| item.name.as_ref().map_or(6, |n| n.chars().count()))
+                .max()
+                .unwrap_or_default();
+            state
+                .items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let (name, name_style) = match &item.name {
+                        Some(name) => (name.as_str(), Style::new()),
+                        None => ("recent", italic),
+                    };
+                    let line = Line::from(vec![
+                        Span::styled(format!("{name:<name_width$}"), name_style),
+                        Span::raw(format!("  {}", item.value)),
+                    ]);
+                    if i == state.selected {
+                        line.style(Style::new().add_modifier(Modifier::REVERSED))
+                    } else {
+                        line
+                    }
+                })
+                .collect()
+        };
+        lines.push(Line::raw(""));
+        lines.push(Line::from(vec![
+            Span::styled("↑↓", bold),
+            Span::styled(" select", italic),
+            Span::raw("  "),
+            Span::styled("enter", bold),
+            Span::styled(" apply", italic),
+            Span::raw("  "),
+            Span::styled("esc", bold),
+            Span::styled(" close", italic),
+        ]));
+
+        let width = lines.iter().map(|l| l.width()).max().unwrap_or_default() as u16 + 4; // + borders + padding
+        let area = area.centered(
+            Constraint::Length(width.min(area.width)),
+            Constraint::Length((lines.len() as u16 + 2).min(area.height)), // + borders
+        );
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .padding(Padding::horizontal(1))
+                    .title(Line::raw(" PRESETS ").centered()),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_select() {
+        let item = |secs| PresetItem {
+            name: None,
+            value: PresetValue::Countdown(Duration::from_secs(secs)),
+        };
+        let mut state = PresetPickerState::new(vec![item(1), item(2)]);
+        assert_eq!(state.get_selected(), Some(item(1).value));
+        state.next();
+        assert_eq!(state.get_selected(), Some(item(2).value));
+        state.next();
+        assert_eq!(state.get_selected(), Some(item(1).value));
+        state.prev();
+        assert_eq!(state.get_selected(), Some(item(2).value));
+
+        let mut state = PresetPickerState::new(vec![]);
+        state.next();
+        state.prev();
+        assert_eq!(state.get_selected(), None);
+    }
+}
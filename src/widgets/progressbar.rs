@@ -1,6 +1,7 @@
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
+    style::{Color, Style},
     symbols::line,
     text::Span,
     widgets::Widget,
@@ -9,11 +10,20 @@ use ratatui::{
 #[derive(Debug, Clone)]
 pub struct Progressbar {
     pub percentage: u16,
+    pub color: Option<Color>,
 }
 
 impl Progressbar {
     pub fn new(percentage: u16) -> Self {
-        Self { percentage }
+        Self {
+            percentage,
+            color: None,
+        }
+    }
+
+    pub fn with_color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
     }
 }
 
@@ -23,7 +33,11 @@ impl Widget for Progressbar {
             Layout::horizontal([Constraint::Percentage(self.percentage), Constraint::Fill(0)])
                 .areas(area);
         // done
-        Span::from(line::THICK_HORIZONTAL.repeat(h1.width as usize)).render(h1, buf);
+        let style = self
+            .color
+            .map(|c| Style::default().fg(c))
+            .unwrap_or_default();
+        Span::styled(line::THICK_HORIZONTAL.repeat(h1.width as usize), style).render(h1, buf);
         // rest
         Span::from(line::HORIZONTAL.repeat(h2.width as usize)).render(h2, buf);
     }
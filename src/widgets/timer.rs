@@ -1,7 +1,8 @@
 use crate::{
     common::Style,
     events::{TuiEvent, TuiEventHandler},
-    widgets::clock::{self, ClockState, ClockWidget},
+    history::History,
+    widgets::clock::{self, ClockSnapshot, ClockState, ClockWidget},
 };
 use crossterm::event::{Event as CrosstermEvent, KeyModifiers};
 use ratatui::{
@@ -11,16 +12,59 @@ use ratatui::{
     text::Line,
     widgets::{StatefulWidget, Widget},
 };
-use std::cmp::max;
+use std::{cmp::max, time::Duration};
 
 pub struct TimerState {
     clock: ClockState<clock::Timer>,
     vim_motions: bool,
+    history: History<ClockSnapshot>,
 }
 
 impl TimerState {
     pub fn new(clock: ClockState<clock::Timer>, vim_motions: bool) -> Self {
-        Self { clock, vim_motions }
+        Self {
+            clock,
+            vim_motions,
+            history: History::default(),
+        }
+    }
+
+    /// Continues running after `duration` has passed in the meantime (e.g. while the app was closed)
+    pub fn resume(&mut self, duration: Duration) {
+        self.clock.resume(duration);
+    }
+
+    /// Starts the timer, which should have been started `duration` ago.
+    /// A finished timer starts again from its initial value.
+    /// A running or edited timer is not changed.
+    pub fn start(&mut self, duration: Duration) {
+        if self.clock.prepare_start() {
+            self.resume(duration);
+        }
+    }
+
+    /// Stores current values to undo next change
+    fn save_snapshot(&mut self) {
+        self.history.push(self.clock.snapshot());
+    }
+
+    /// Stores values taken `before` a change to undo it, but only if something has changed
+    fn save_changed_snapshot(&mut self, before: ClockSnapshot) {
+        if before != self.clock.snapshot() {
+            self.history.push(before);
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(snapshot) = self.history.undo(self.clock.snapshot()) {
+            self.clock.restore(snapshot);
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(snapshot) = self.history.redo(self.clock.snapshot()) {
+            self.clock.restore(snapshot);
+        }
     }
 
     pub fn set_with_decis(&mut self, with_decis: bool) {
@@ -51,7 +95,9 @@ impl TuiEventHandler for TimerState {
                 }
                 // Apply changes
                 KeyCode::Char('s') => {
+                    let before = self.clock.snapshot();
                     self.clock.toggle_edit();
+                    self.save_changed_snapshot(before);
                 }
                 // move change position to the left
                 KeyCode::Left if !self.vim_motions => {
@@ -107,8 +153,15 @@ impl TuiEventHandler for TimerState {
                 }
                 // reset clock
                 KeyCode::Char('r') => {
+                    self.save_snapshot();
                     self.clock.reset();
                 }
+                KeyCode::Char('u') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.undo();
+                }
+                KeyCode::Char('U') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.redo();
+                }
                 // enter edit mode
                 KeyCode::Char('e') => {
                     self.clock.toggle_edit();